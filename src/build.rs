@@ -0,0 +1,507 @@
+//! Workspace for planning a full build out of one item per equipment slot
+
+use sycamore::prelude::*;
+
+use crate::{
+    damage::id_value,
+    powders::apply_armour_powders,
+    powderselect::PowderSelect,
+    read_ids,
+    registry::IdRegistry,
+    skillpoints::SKILL_POINT_IDS,
+    slider::Slider,
+    solver::{solve, Solution, SolveError, SolverItem},
+    tooltip::{element_color, formatnum},
+    wynn::items::{Id, Identification, Item, ItemList, Powder, Powders, StatusType, Type},
+};
+
+/// An equipment slot of a build.
+///
+/// The slots are declared in the order of `Slot::ALL` so they can be used as indexes into it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Helmet,
+    Chestplate,
+    Leggings,
+    Boots,
+    Ring1,
+    Ring2,
+    Bracelet,
+    Necklace,
+    Weapon,
+}
+
+impl Slot {
+    pub const ALL: [Slot; 9] = [
+        Slot::Helmet,
+        Slot::Chestplate,
+        Slot::Leggings,
+        Slot::Boots,
+        Slot::Ring1,
+        Slot::Ring2,
+        Slot::Bracelet,
+        Slot::Necklace,
+        Slot::Weapon,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Slot::Helmet => "Helmet",
+            Slot::Chestplate => "Chestplate",
+            Slot::Leggings => "Leggings",
+            Slot::Boots => "Boots",
+            Slot::Ring1 => "Ring 1",
+            Slot::Ring2 => "Ring 2",
+            Slot::Bracelet => "Bracelet",
+            Slot::Necklace => "Necklace",
+            Slot::Weapon => "Weapon",
+        }
+    }
+
+    /// Whether items of the type can be put in the slot
+    pub fn accepts(self, r#type: &Type) -> bool {
+        match self {
+            Slot::Helmet => *r#type == Type::HELMET,
+            Slot::Chestplate => *r#type == Type::CHESTPLATE,
+            Slot::Leggings => *r#type == Type::LEGGINGS,
+            Slot::Boots => *r#type == Type::BOOTS,
+            Slot::Ring1 | Slot::Ring2 => *r#type == Type::RING,
+            Slot::Bracelet => *r#type == Type::BRACELET,
+            Slot::Necklace => *r#type == Type::NECKLACE,
+            Slot::Weapon => r#type.is_weapon(),
+        }
+    }
+}
+
+/// An item placed in a build slot with its own rolls and powders
+#[derive(Clone, PartialEq)]
+pub struct BuildItem {
+    pub item: Item,
+    pub ids: Vec<Id>,
+    pub powders: Vec<RcSignal<Option<Powder>>>,
+}
+
+impl BuildItem {
+    pub fn new(item: &Item, registry: &IdRegistry) -> Self {
+        Self {
+            item: item.clone(),
+            ids: read_ids(item, registry),
+            powders: (0..item.max_powders)
+                .map(|_| create_rc_signal(None))
+                .collect(),
+        }
+    }
+}
+
+/// Skill point requirements of a single item compared to the skill points of the build
+#[derive(Clone, PartialEq, Eq)]
+pub struct RequirementCheck {
+    pub slot: Slot,
+    pub name: String,
+    pub level: i32,
+    /// Required skill points in the strength, dexterity, intelligence, defence, agility order
+    pub required: [i32; 5],
+    /// Whether the skill points of the build meet each requirement
+    pub met: [bool; 5],
+}
+
+/// Combined stats of every item in a build
+#[derive(Clone, PartialEq, Eq)]
+pub struct BuildSummary {
+    /// Totals of every id in the build in the wynntils order
+    pub ids: Vec<(Identification, StatusType, i32)>,
+    pub health: i32,
+    /// Elemental defences in the earth, thunder, water, fire, air order
+    pub defenses: [i32; 5],
+    /// Skill points given by the ids of the items
+    pub skill_points: [i32; 5],
+    /// Skill points to assign and the equip order meeting every requirement
+    pub solution: Result<Solution, SolveError>,
+    pub requirements: Vec<RequirementCheck>,
+}
+
+/// Totals the ids, defences and skill points of the items in a build.
+///
+/// The solution of the skill point requirements is passed in as solving it is much slower than the
+/// rest and only has to be redone when the requirements or skill point bonuses change.
+pub fn summarize(
+    items: &[(Slot, BuildItem)],
+    registry: &IdRegistry,
+    solution: Result<Solution, SolveError>,
+) -> BuildSummary {
+    let mut ids = Vec::new();
+    for id in registry.sorted() {
+        let mut total = None;
+        for (_, item) in items {
+            if let Some(found) = item.ids.iter().find(|i| i.id == *id) {
+                let (idtype, value) = total.unwrap_or((found.idtype, 0));
+                total = Some((idtype, value + *found.value.get()));
+            }
+        }
+
+        if let Some((idtype, value)) = total {
+            ids.push((id.clone(), idtype, value));
+        }
+    }
+
+    let mut health = 0;
+    let mut defenses = [0; 5];
+    for (_, item) in items {
+        if let Some(defs) = &item.item.defenses {
+            health += defs.health.unwrap_or(0);
+
+            let powders = item
+                .powders
+                .iter()
+                .filter_map(|p| *p.get())
+                .collect::<Vec<_>>();
+
+            // weapon powders do not give defence
+            let powders = if item.item.item_info.r#type.is_armour() {
+                powders
+            } else {
+                Vec::new()
+            };
+
+            for (total, d) in defenses
+                .iter_mut()
+                .zip(apply_armour_powders(defs, &powders))
+            {
+                *total += d.map(|d| d.value).unwrap_or(0);
+            }
+        }
+    }
+
+    let mut skill_points = [0; 5];
+    for (_, item) in items {
+        for (points, id) in skill_points.iter_mut().zip(SKILL_POINT_IDS) {
+            *points += id_value(&item.ids, id);
+        }
+    }
+
+    // compare against the assigned points too if the requirements can be met
+    let available = match &solution {
+        Ok(solution) => std::array::from_fn(|i| skill_points[i] + solution.assigned[i]),
+        Err(_) => skill_points,
+    };
+
+    let requirements = items
+        .iter()
+        .map(|(slot, item)| {
+            let required = item.item.requirements.skill_points();
+            // an item cannot be equipped using its own skill points
+            let own = SKILL_POINT_IDS.map(|id| id_value(&item.ids, id).max(0));
+            let met =
+                std::array::from_fn(|i| required[i] <= 0 || required[i] <= available[i] - own[i]);
+
+            RequirementCheck {
+                slot: *slot,
+                name: item.item.name.clone(),
+                level: item.item.requirements.level.unwrap_or(1),
+                required,
+                met,
+            }
+        })
+        .collect();
+
+    BuildSummary {
+        ids,
+        health,
+        defenses,
+        skill_points,
+        solution,
+        requirements,
+    }
+}
+
+#[derive(Prop)]
+pub struct BuildWorkspaceProps<'a> {
+    item_list: &'a ItemList,
+    registry: &'a IdRegistry,
+}
+
+#[component]
+pub fn BuildWorkspace<'a, G: Html>(cx: Scope<'a>, props: BuildWorkspaceProps<'a>) -> View<G> {
+    let registry = props.registry;
+
+    let slots = create_ref(
+        cx,
+        Slot::ALL.map(|slot| (slot, create_rc_signal(None::<BuildItem>))),
+    );
+
+    let items = create_selector(cx, move || {
+        slots
+            .iter()
+            .filter_map(|(slot, item)| (*item.get()).clone().map(|item| (*slot, item)))
+            .collect::<Vec<_>>()
+    });
+
+    // only solve again when the inputs of the solver change
+    let solver_items = create_selector(cx, move || {
+        items
+            .get()
+            .iter()
+            .map(|(_, item)| SolverItem::new(&item.item, &item.ids))
+            .collect::<Vec<_>>()
+    });
+    let solution = create_selector(cx, move || solve(&solver_items.get()));
+
+    let summary = create_selector(cx, move || {
+        summarize(&items.get(), registry, (*solution.get()).clone())
+    });
+
+    // spell costs are named after the class of the weapon
+    let weapon_type = create_selector(cx, move || {
+        slots[Slot::Weapon as usize]
+            .1
+            .get()
+            .as_ref()
+            .as_ref()
+            .map(|i| i.item.item_info.r#type.clone())
+            .unwrap_or(Type::INVALID)
+    });
+
+    let editors = View::new_fragment(
+        slots
+            .iter()
+            .map(|(slot, item)| {
+                view! {cx,
+                    div(class="pure-u-1 pure-u-md-1-3") {
+                        div(style="margin: 5px;") {
+                            SlotEditor(slot=*slot, item_list=props.item_list, registry=registry, item=item)
+                        }
+                    }
+                }
+            })
+            .collect(),
+    );
+
+    view! {cx,
+        div(class="pure-g") {(editors)}
+
+        h3 {"Totals"}
+        (if summary.get().health != 0 {
+            view!{cx,
+                span(class="mc-dark-red") {(format!("❤ Health: {}", summary.get().health))}
+                br {}
+            }
+        } else {view!{cx,}})
+        (View::new_fragment(
+            summary
+                .get()
+                .defenses
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| **d != 0)
+                .filter_map(|(i, d)| Powders::from_i32(i as i32).map(|element| (element, *d)))
+                .map(|(element, d)| view!{cx,
+                    span(class=element_color(element).class()) {(format!("{} {element} ", element.symbol()))}
+                    span {(format!("Defence: {d}"))}
+                    br {}
+                })
+                .collect(),
+        ))
+        br {}
+        (View::new_fragment(
+            summary
+                .get()
+                .ids
+                .iter()
+                .map(|(id, idtype, value)| {
+                    let text = format!(
+                        "{}{} {}",
+                        formatnum(*value),
+                        registry.unit(id, *idtype),
+                        registry.name(id, &weapon_type.get())
+                    );
+                    view!{cx,
+                        span {(text)}
+                        br {}
+                    }
+                })
+                .collect(),
+        ))
+
+        h3 {"Skill points"}
+        table(class="pure-table", style="width: 100%;") {
+            thead {
+                tr {
+                    th {"Slot"}
+                    th {"Item"}
+                    th {"Lv"}
+                    th {"Str"}
+                    th {"Dex"}
+                    th {"Int"}
+                    th {"Def"}
+                    th {"Agi"}
+                }
+            }
+            tbody {
+                tr {
+                    td {}
+                    td {"From ids"}
+                    td {}
+                    (View::new_fragment(
+                        summary.get().skill_points.iter().map(|p| {
+                            let p = *p;
+                            view!{cx, td {(p)}}
+                        }).collect(),
+                    ))
+                }
+                (if let Ok(solution) = &summary.get().solution {
+                    let cells = View::new_fragment(
+                        solution.assigned.iter().map(|p| {
+                            let p = *p;
+                            view!{cx, td {(p)}}
+                        }).collect(),
+                    );
+                    view!{cx,
+                        tr {
+                            td {}
+                            td {"Assigned"}
+                            td {}
+                            (cells)
+                        }
+                    }
+                } else {view!{cx,}})
+                Indexed (
+                    iterable=create_selector(cx, move || summary.get().requirements.clone()),
+                    view=|cx, check| {
+                        let cells = View::new_fragment(
+                            check
+                                .required
+                                .iter()
+                                .zip(check.met)
+                                .map(|(required, met)| {
+                                    let required = *required;
+                                    let class = if required == 0 {
+                                        ""
+                                    } else if met {
+                                        "mc-green"
+                                    } else {
+                                        "mc-red"
+                                    };
+                                    view!{cx, td(class=class) {(required)}}
+                                })
+                                .collect(),
+                        );
+
+                        view! {cx,
+                            tr {
+                                td {(check.slot.name())}
+                                td {(check.name)}
+                                td {(check.level)}
+                                (cells)
+                            }
+                        }
+                    }
+                )
+            }
+        }
+        (match &summary.get().solution {
+            Ok(solution) if !solution.order.is_empty() => {
+                let order = solution
+                    .order
+                    .iter()
+                    .map(|i| summary.get().requirements[*i].name.clone())
+                    .collect::<Vec<_>>()
+                    .join(" → ");
+                let text = format!("Assign {} skill points and equip in this order: {order}", solution.total());
+                view!{cx, p {(text)}}
+            }
+            Ok(_) => view!{cx,},
+            Err(e) => {
+                let text = e.to_string();
+                view!{cx, p(class="mc-red") {(text)}}
+            }
+        })
+    }
+}
+
+#[derive(Prop)]
+struct SlotEditorProps<'a> {
+    slot: Slot,
+    item_list: &'a ItemList,
+    registry: &'a IdRegistry,
+    item: &'a RcSignal<Option<BuildItem>>,
+}
+
+/// Item search, id sliders and powders of a single build slot
+#[component]
+fn SlotEditor<'a, G: Html>(cx: Scope<'a>, props: SlotEditorProps<'a>) -> View<G> {
+    let slot = props.slot;
+    let item = props.item;
+    let searchtext = create_signal(cx, String::new());
+
+    // only offer items fitting the slot
+    let list_id = create_ref(cx, format!("build-items-{}", slot as usize));
+    let itemnames = View::new_fragment(
+        props
+            .item_list
+            .items
+            .iter()
+            .filter(|i| slot.accepts(&i.item_info.r#type))
+            .map(|i| {
+                let name = i.name.clone();
+                view! {cx, option(value=name)}
+            })
+            .collect(),
+    );
+
+    create_effect(cx, move || {
+        let found = props.item_list.items.iter().find(|i| {
+            slot.accepts(&i.item_info.r#type) && i.name.eq_ignore_ascii_case(&searchtext.get())
+        });
+
+        let current = item
+            .get_untracked()
+            .as_ref()
+            .as_ref()
+            .map(|i| i.item.name.clone());
+        match found {
+            Some(found) if current.as_ref() != Some(&found.name) => {
+                item.set(Some(BuildItem::new(found, props.registry)))
+            }
+            None if current.is_some() => item.set(None),
+            _ => {}
+        }
+    });
+
+    view! {cx,
+        h4(style="margin: 0;") {(slot.name())}
+        form(class="pure-form") {
+            input(style="width: 100%;", prop:type="search", placeholder="Item name", list=list_id, bind:value=searchtext)
+            datalist(id=list_id.clone()) {(itemnames)}
+        }
+        (if let Some(selected) = (*item.get()).clone() {
+            let sliders = View::new_fragment(
+                selected
+                    .ids
+                    .iter()
+                    .filter(|id| !id.encoding().is_fixed())
+                    .map(|id| {
+                        let name = props.registry.name(&id.id, &selected.item.item_info.r#type);
+                        view! {cx,
+                            Slider(name=name, min=id.min_id(), max=id.max_id(), value=id.value.clone())
+                        }
+                    })
+                    .collect(),
+            );
+            let powders = View::new_fragment(
+                selected
+                    .powders
+                    .iter()
+                    .map(|powder| view! {cx, PowderSelect(powder=powder.clone())})
+                    .collect(),
+            );
+
+            view! {cx,
+                details {
+                    summary {"IDs and powders"}
+                    (sliders)
+                    div(style="display: flex; width: 100%; flex-wrap: wrap; gap: 10px;") {(powders)}
+                }
+            }
+        } else {view!{cx,}})
+    }
+}
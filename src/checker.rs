@@ -0,0 +1,196 @@
+use sycamore::prelude::*;
+
+use crate::{
+    encoding::{decode_string, decode_value},
+    read_ids,
+    registry::{Encoding, IdRegistry},
+    wynn::items::ItemList,
+};
+
+#[derive(Prop)]
+pub struct CheckerProps<'a> {
+    item_list: &'a ItemList,
+    registry: &'a IdRegistry,
+}
+
+/// Result of checking a single part of an id string
+#[derive(Clone, PartialEq, Eq)]
+pub struct Verdict {
+    /// Which part of the string was checked
+    pub field: String,
+    /// Whether that part could have come from a real item
+    pub plausible: bool,
+    pub message: String,
+}
+
+impl Verdict {
+    fn new(field: impl Into<String>, plausible: bool, message: String) -> Self {
+        Self {
+            field: field.into(),
+            plausible,
+            message,
+        }
+    }
+}
+
+/// Checks whether an id string could have come from a real item
+pub fn check(s: &str, item_list: &ItemList, registry: &IdRegistry) -> Vec<Verdict> {
+    let mut verdicts = Vec::new();
+
+    let decoded = match decode_string(s) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            verdicts.push(Verdict::new("String", false, e.to_string()));
+            return verdicts;
+        }
+    };
+
+    let item = if let Some(item) = item_list.items.iter().find(|i| i.name == decoded.name) {
+        verdicts.push(Verdict::new("Name", true, format!("{} exists", item.name)));
+        item
+    } else {
+        verdicts.push(Verdict::new(
+            "Name",
+            false,
+            format!("There is no item called {}", decoded.name),
+        ));
+        return verdicts;
+    };
+
+    // only ids which are not fixed are stored in the string
+    let ids = read_ids(item, registry)
+        .into_iter()
+        .filter(|id| id.encoding() != Encoding::Fixed)
+        .collect::<Vec<_>>();
+
+    if ids.len() == decoded.ids.len() {
+        verdicts.push(Verdict::new(
+            "Identifications",
+            true,
+            format!("{} identifications as expected", ids.len()),
+        ));
+    } else {
+        verdicts.push(Verdict::new(
+            "Identifications",
+            false,
+            format!(
+                "{} has {} identifications which are not fixed but the string has {}",
+                item.name,
+                ids.len(),
+                decoded.ids.len()
+            ),
+        ));
+    }
+
+    for (id, encoded) in ids.iter().zip(&decoded.ids) {
+        // these ids always have the same value and are always stored as 0
+        if id.encoding() == Encoding::Constant {
+            let name = registry.name(&id.id, &item.item_info.r#type);
+            if *encoded == 0 {
+                verdicts.push(Verdict::new(
+                    name,
+                    true,
+                    String::from("Always has the same value"),
+                ));
+            } else {
+                verdicts.push(Verdict::new(
+                    name,
+                    false,
+                    format!("Stored as {encoded} but this id can only be stored as 0"),
+                ));
+            }
+            continue;
+        }
+
+        let value = decode_value(id, *encoded);
+
+        if (id.min_id()..=id.max_id()).contains(&value) {
+            verdicts.push(Verdict::new(
+                registry.name(&id.id, &item.item_info.r#type),
+                true,
+                format!("{value} is a possible roll"),
+            ));
+        } else {
+            verdicts.push(Verdict::new(
+                registry.name(&id.id, &item.item_info.r#type),
+                false,
+                format!(
+                    "{value} is outside of the roll range {} to {}",
+                    id.min_id(),
+                    id.max_id()
+                ),
+            ));
+        }
+    }
+
+    if decoded.powders.len() <= item.max_powders as usize {
+        verdicts.push(Verdict::new(
+            "Powders",
+            true,
+            format!(
+                "{}/{} powder slots used",
+                decoded.powders.len(),
+                item.max_powders
+            ),
+        ));
+    } else {
+        verdicts.push(Verdict::new(
+            "Powders",
+            false,
+            format!(
+                "{} powders applied but the item only has {} powder slots",
+                decoded.powders.len(),
+                item.max_powders
+            ),
+        ));
+    }
+
+    verdicts.push(Verdict::new(
+        "Rerolls",
+        true,
+        format!("Rerolled {} times", decoded.rerolls),
+    ));
+
+    verdicts
+}
+
+#[component]
+pub fn Checker<'a, G: Html>(cx: Scope<'a>, props: CheckerProps<'a>) -> View<G> {
+    let input = create_signal(cx, String::new());
+
+    let verdicts = create_selector(cx, move || {
+        if input.get().trim().is_empty() {
+            Vec::new()
+        } else {
+            check(&input.get(), props.item_list, props.registry)
+        }
+    });
+
+    view! {cx,
+        form(class="pure-form") {
+            textarea(style="width: 100%;", placeholder="Paste an item string", bind:value=input)
+        }
+        table(class="pure-table", style="width: 100%;") {
+            tbody {
+                Indexed (
+                    iterable=verdicts,
+                    view=|cx, verdict| {
+                        let (class, symbol) = if verdict.plausible {
+                            ("mc-green", "✔")
+                        } else {
+                            ("mc-red", "✘")
+                        };
+
+                        view! {cx,
+                            tr {
+                                td(class=class) {(symbol)}
+                                td {(verdict.field)}
+                                td {(verdict.message)}
+                            }
+                        }
+                    }
+                )
+            }
+        }
+    }
+}
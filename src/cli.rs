@@ -0,0 +1,235 @@
+//! Command line interface for rendering items outside the browser
+//!
+//! Usage: `idmangler <item list json> <item name or id string> [--png FILE] [--svg FILE] [--scale N] [--give] [--color MODE] [--pieces N] [--skill-points S,D,I,D,A] [--profile FILE] [--range-weighted] [--powder-tier N]`
+//!
+//! Without output options the tooltip is printed to the terminal, colored according to `--color`
+//! (`truecolor`, `256` or `never`) or what the terminal reports. The tooltip can be given the same
+//! settings as the preview: set pieces equipped, assumed skill points, a weight profile exported from
+//! the page and range weighted overall %. Id strings do not store powder tiers so decoded powders are
+//! tier VI unless `--powder-tier` says otherwise.
+
+use crate::{
+    encoding::{decode_string, decode_value},
+    minecraft::give_command,
+    png::{render_png, MAX_SCALE, MIN_SCALE},
+    read_ids,
+    registry::{Encoding, IdRegistry},
+    sets::{find_set, load_sets},
+    svg::render_svg,
+    terminal::{render_terminal, ColorMode},
+    tooltip::{tooltip, Line, OverallMode, TooltipInput},
+    weights::WeightProfile,
+    wynn::items::{Item, ItemList, Powder},
+};
+
+/// Options given on the command line
+struct Args {
+    item_list: String,
+    item: String,
+    png: Option<String>,
+    svg: Option<String>,
+    scale: usize,
+    give: bool,
+    color: Option<ColorMode>,
+    set_pieces: i32,
+    skill_points: Option<[i32; 5]>,
+    profile: Option<String>,
+    overall_mode: OverallMode,
+    /// Tier of the powders decoded from an id string if one was given
+    powder_tier: Option<u8>,
+}
+
+/// Usage shown when the arguments cannot be read
+const USAGE: &str = "usage: idmangler <item list json> <item name or id string> [--png FILE] [--svg FILE] [--scale N] [--give] [--color MODE] [--pieces N] [--skill-points S,D,I,D,A] [--profile FILE] [--range-weighted] [--powder-tier N]";
+
+fn parse_args() -> Result<Args, String> {
+    let mut positional = Vec::new();
+    let mut png = None;
+    let mut svg = None;
+    let mut scale = 2;
+    let mut give = false;
+    let mut color = None;
+    let mut set_pieces = 1;
+    let mut skill_points = None;
+    let mut profile = None;
+    let mut overall_mode = OverallMode::Average;
+    let mut powder_tier = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--png" => png = Some(args.next().ok_or("--png needs a file")?),
+            "--svg" => svg = Some(args.next().ok_or("--svg needs a file")?),
+            "--scale" => {
+                scale = args
+                    .next()
+                    .and_then(|s| s.parse().ok())
+                    .filter(|s| (MIN_SCALE..=MAX_SCALE).contains(s))
+                    .ok_or(format!(
+                        "--scale needs a number from {MIN_SCALE} to {MAX_SCALE}"
+                    ))?
+            }
+            "--give" => give = true,
+            "--color" => {
+                color = Some(
+                    args.next()
+                        .and_then(|s| ColorMode::parse(&s))
+                        .ok_or("--color needs one of truecolor, 256 or never")?,
+                )
+            }
+            "--pieces" => {
+                set_pieces = args
+                    .next()
+                    .and_then(|s| s.parse().ok())
+                    .ok_or("--pieces needs a number")?
+            }
+            "--skill-points" => {
+                let points = args
+                    .next()
+                    .map(|s| s.split(',').map(|p| p.trim().parse()).collect::<Vec<_>>())
+                    .and_then(|p| p.into_iter().collect::<Result<Vec<i32>, _>>().ok())
+                    .and_then(|p| <[i32; 5]>::try_from(p).ok())
+                    .ok_or("--skill-points needs 5 numbers separated by commas")?;
+                skill_points = Some(points);
+            }
+            "--profile" => profile = Some(args.next().ok_or("--profile needs a file")?),
+            "--range-weighted" => overall_mode = OverallMode::RangeWeighted,
+            "--powder-tier" => {
+                powder_tier = Some(
+                    args.next()
+                        .and_then(|s| s.parse().ok())
+                        .filter(|t| (1..=6).contains(t))
+                        .ok_or("--powder-tier needs a number from 1 to 6")?,
+                )
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {arg}\n{USAGE}")),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    match (positional.next(), positional.next(), positional.next()) {
+        (Some(item_list), Some(item), None) => Ok(Args {
+            item_list,
+            item,
+            png,
+            svg,
+            scale,
+            give,
+            color,
+            set_pieces,
+            skill_points,
+            profile,
+            overall_mode,
+            powder_tier,
+        }),
+        (_, _, Some(extra)) => Err(format!("Unexpected argument {extra}\n{USAGE}")),
+        _ => Err(String::from(USAGE)),
+    }
+}
+
+/// Builds the tooltip of an item given by name or by an id string
+fn item_tooltip<'a>(
+    item_list: &'a ItemList,
+    registry: &IdRegistry,
+    args: &Args,
+    profile: Option<&WeightProfile>,
+) -> Result<(&'a Item, Vec<Line>), String> {
+    let item = args.item.as_str();
+    let decoded = if item.contains(crate::encoding::START) {
+        Some(decode_string(item).map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+    let name = decoded.as_ref().map(|d| d.name.as_str()).unwrap_or(item);
+
+    let item = item_list
+        .items
+        .iter()
+        .find(|i| i.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("There is no item called {name}"))?;
+
+    let ids = read_ids(item, registry);
+    let mut powders = vec![None; item.max_powders as usize];
+    let mut rerolls = 0;
+
+    // take the rolls from the id string
+    if let Some(decoded) = decoded {
+        let rolled = ids.iter().filter(|id| id.encoding() != Encoding::Fixed);
+        for (id, encoded) in rolled.zip(decoded.ids) {
+            id.value.set(decode_value(id, encoded));
+        }
+        // id strings only store the elements of the powders
+        let tier = args.powder_tier.unwrap_or(6);
+        if args.powder_tier.is_none() && !decoded.powders.is_empty() {
+            eprintln!("Warning: id strings do not store powder tiers, assuming tier VI (see --powder-tier)");
+        }
+        for (slot, element) in powders.iter_mut().zip(decoded.powders) {
+            *slot = Some(Powder { element, tier });
+        }
+        rerolls = decoded.rerolls;
+    }
+
+    let lines = tooltip(&TooltipInput {
+        item,
+        ids: &ids,
+        powders: &powders,
+        rerolls,
+        registry,
+        overall_mode: args.overall_mode,
+        skill_points: args.skill_points,
+        profile,
+        set: find_set(item, &load_sets(item_list)),
+        set_pieces: args.set_pieces,
+    });
+
+    Ok((item, lines))
+}
+
+fn try_run() -> Result<(), String> {
+    let args = parse_args()?;
+
+    let json = std::fs::read_to_string(&args.item_list)
+        .map_err(|e| format!("Reading {} failed: {e}", args.item_list))?;
+    let item_list: ItemList = serde_json::from_str(&json)
+        .map_err(|e| format!("Parsing {} failed: {e}", args.item_list))?;
+    let registry = IdRegistry::new(&item_list);
+
+    let profile = match &args.profile {
+        Some(file) => {
+            let json =
+                std::fs::read_to_string(file).map_err(|e| format!("Reading {file} failed: {e}"))?;
+            let profile: WeightProfile =
+                serde_json::from_str(&json).map_err(|e| format!("Parsing {file} failed: {e}"))?;
+            Some(profile)
+        }
+        None => None,
+    };
+
+    let (item, lines) = item_tooltip(&item_list, &registry, &args, profile.as_ref())?;
+
+    if let Some(file) = &args.png {
+        std::fs::write(file, render_png(&lines, args.scale))
+            .map_err(|e| format!("Writing {file} failed: {e}"))?;
+    }
+    if let Some(file) = &args.svg {
+        std::fs::write(file, render_svg(&lines))
+            .map_err(|e| format!("Writing {file} failed: {e}"))?;
+    }
+    if args.give {
+        println!("{}", give_command(item, &lines).map_err(|e| e.to_string())?);
+    }
+    if args.png.is_none() && args.svg.is_none() && !args.give {
+        let mode = args.color.unwrap_or_else(ColorMode::detect);
+        print!("{}", render_terminal(&lines, mode));
+    }
+
+    Ok(())
+}
+
+pub fn run() {
+    if let Err(e) = try_run() {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
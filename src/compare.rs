@@ -0,0 +1,439 @@
+//! Side by side comparison of two items or two rolls of an item
+
+use sycamore::prelude::*;
+
+use crate::{
+    damage::average_damage,
+    export::{create_tooltip, TooltipSettings},
+    itemrender::ItemRender,
+    powders::{apply_armour_powders, apply_weapon_powders},
+    read_ids,
+    registry::{base_name, IdRegistry},
+    solver::SKILL_NAMES,
+    tooltip::{Line, OverallMode},
+    wynn::items::{Id, Item, ItemList, ItemSet, Powder, Powders},
+};
+
+/// Side of the comparison with the better value
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Better {
+    Left,
+    Right,
+}
+
+/// A single compared stat
+#[derive(Clone, PartialEq)]
+pub struct DiffRow {
+    pub label: String,
+    pub left: Option<f64>,
+    pub right: Option<f64>,
+    /// Whether lower values are better like with spell costs and requirements
+    pub lower_better: bool,
+}
+
+impl DiffRow {
+    fn new(
+        label: impl Into<String>,
+        left: Option<f64>,
+        right: Option<f64>,
+        lower_better: bool,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            left,
+            right,
+            lower_better,
+        }
+    }
+
+    /// Difference from the left to the right side
+    pub fn delta(&self) -> f64 {
+        self.right.unwrap_or(0.0) - self.left.unwrap_or(0.0)
+    }
+
+    pub fn better(&self) -> Option<Better> {
+        let delta = self.delta();
+        if delta == 0.0 {
+            None
+        } else if (delta > 0.0) != self.lower_better {
+            Some(Better::Right)
+        } else {
+            Some(Better::Left)
+        }
+    }
+}
+
+/// An item with its rolls and applied powders
+type Compared<'a> = (&'a Item, &'a [Id], &'a [Powder]);
+
+/// Compares the damages, defences, requirements and ids of two items.
+///
+/// Damages and defences include the powders like the tooltip does.
+pub fn diff(left: Compared, right: Compared, registry: &IdRegistry) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+
+    // damages
+    let damages = |(item, ids, powders): Compared| {
+        let Some(ranges) = item.damages.as_ref().map(|d| d.ranges()) else {
+            return ([None; 6], None);
+        };
+        let ranges = ranges.map(|r| r.and_then(|r| r.ok()));
+        let powders = if item.item_info.r#type.is_weapon() {
+            powders
+        } else {
+            &[]
+        };
+        let damages = apply_weapon_powders(&ranges, powders);
+        (
+            damages.map(|d| d.map(|d| d.value.average())),
+            item.speed.map(|speed| average_damage(&damages, speed, ids)),
+        )
+    };
+    let ((l, l_average), (r, r_average)) = (damages(left), damages(right));
+    for i in 0..6 {
+        if l[i].is_some() || r[i].is_some() {
+            let name = match Powders::from_i32(i as i32 - 1) {
+                Some(element) => format!("{element} Damage"),
+                None => String::from("Neutral Damage"),
+            };
+            rows.push(DiffRow::new(name, l[i], r[i], false));
+        }
+    }
+    if l_average.is_some() || r_average.is_some() {
+        rows.push(DiffRow::new(
+            "Average Damage",
+            l_average.map(|a| a.per_hit),
+            r_average.map(|a| a.per_hit),
+            false,
+        ));
+        rows.push(DiffRow::new(
+            "Average DPS",
+            l_average.map(|a| a.per_second),
+            r_average.map(|a| a.per_second),
+            false,
+        ));
+    }
+
+    // defences
+    let defenses = |(item, _, powders): Compared| {
+        let Some(defenses) = &item.defenses else {
+            return [None; 6];
+        };
+        let powders = if item.item_info.r#type.is_armour() {
+            powders
+        } else {
+            &[]
+        };
+        let elements = apply_armour_powders(defenses, powders);
+        let mut out = [None; 6];
+        out[0] = defenses.health.map(|h| h as f64);
+        for (out, d) in out[1..].iter_mut().zip(elements) {
+            *out = d.map(|d| d.value as f64);
+        }
+        out
+    };
+    let (l, r) = (defenses(left), defenses(right));
+    for i in 0..6 {
+        if l[i].is_some() || r[i].is_some() {
+            let name = match Powders::from_i32(i as i32 - 1) {
+                Some(element) => format!("{element} Defence"),
+                None => String::from("Health"),
+            };
+            rows.push(DiffRow::new(name, l[i], r[i], false));
+        }
+    }
+
+    // requirements
+    let (l, r) = (
+        left.0.requirements.level.map(|l| l as f64),
+        right.0.requirements.level.map(|l| l as f64),
+    );
+    if l.is_some() || r.is_some() {
+        rows.push(DiffRow::new("Level Req", l, r, true));
+    }
+    let (l, r) = (
+        left.0.requirements.skill_points(),
+        right.0.requirements.skill_points(),
+    );
+    for (i, name) in SKILL_NAMES.iter().enumerate() {
+        if l[i] != 0 || r[i] != 0 {
+            rows.push(DiffRow::new(
+                format!("{name} Min"),
+                Some(l[i] as f64),
+                Some(r[i] as f64),
+                true,
+            ));
+        }
+    }
+
+    // ids in the wynntils order
+    for id in registry.sorted() {
+        let value = |ids: &[Id]| {
+            ids.iter()
+                .find(|i| i.id == *id)
+                .map(|i| *i.value.get() as f64)
+        };
+        let (l, r) = (value(left.1), value(right.1));
+        if l.is_some() || r.is_some() {
+            // spell costs are only named after the spells when both items are of the same class
+            let name = registry.name(id, &left.0.item_info.r#type);
+            let name = if name == registry.name(id, &right.0.item_info.r#type) {
+                name
+            } else {
+                base_name(id)
+            };
+            rows.push(DiffRow::new(name, l, r, registry.is_inverted(id)));
+        }
+    }
+
+    rows
+}
+
+/// Powders put into the slots of an item
+fn applied(powders: &[RcSignal<Option<Powder>>]) -> Vec<Powder> {
+    powders.iter().filter_map(|p| *p.get()).collect()
+}
+
+/// Rolls every id of an item to the same point of its range
+fn roll_ids(ids: &[Id], roll: &str, registry: &IdRegistry) {
+    for id in ids {
+        let (best, worst) = if registry.is_inverted(&id.id) {
+            (id.min_id(), id.max_id())
+        } else {
+            (id.max_id(), id.min_id())
+        };
+
+        id.value.set(match roll {
+            "perfect" => best,
+            "worst" => worst,
+            "average" => f64::round((best + worst) as f64 / 2.0) as i32,
+            // other items have no editor roll so they show their base values
+            _ => id.baseval,
+        });
+    }
+}
+
+/// Number without a trailing .0
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+#[derive(Prop)]
+pub struct CompareProps<'a> {
+    item_list: &'a ItemList,
+    registry: &'a IdRegistry,
+    sets: &'a [ItemSet],
+    /// Item and rolls of the editor
+    editor_item: &'a ReadSignal<Option<Item>>,
+    editor_ids: &'a ReadSignal<Vec<Id>>,
+    editor_powders: &'a ReadSignal<Vec<RcSignal<Option<Powder>>>>,
+    editor_rerolls: &'a ReadSignal<i32>,
+    overall_mode: &'a ReadSignal<OverallMode>,
+}
+
+#[component]
+pub fn Compare<'a, G: Html>(cx: Scope<'a>, props: CompareProps<'a>) -> View<G> {
+    // the same item rolled perfectly is the most common comparison
+    let left = side(cx, &props, "editor");
+    let right = side(cx, &props, "perfect");
+
+    let rows = create_selector(cx, move || match (&*left.item.get(), &*right.item.get()) {
+        (Some(l), Some(r)) => diff(
+            (l, &left.ids.get(), &applied(&left.powders.get())),
+            (r, &right.ids.get(), &applied(&right.powders.get())),
+            props.registry,
+        ),
+        _ => Vec::new(),
+    });
+
+    let panel = move |side: &'a Side<'a>| {
+        view! {cx,
+            form(class="pure-form", style="display: flex; gap: 5px; margin-bottom: 5px;") {
+                input(style="flex-grow: 1;", prop:type="search", placeholder="Item name (editor item if empty)", list="items", bind:value=side.name)
+                select(bind:value=side.roll) {
+                    option(value="editor") {"Editor roll"}
+                    option(value="perfect") {"Perfect"}
+                    option(value="average") {"Average"}
+                    option(value="worst") {"Worst"}
+                    option(value="base") {"Base values"}
+                }
+            }
+            (if *side.base_fallback.get() {
+                view! {cx, p(class="problem mc-gold") {"The editor roll only applies to the editor item, showing base values"}}
+            } else {view!{cx,}})
+            (if side.item.get().is_some() {
+                view! {cx,
+                    ItemRender(lines=side.lines)
+                }
+            } else {view!{cx,}})
+        }
+    };
+
+    view! {cx,
+        div(class="pure-g") {
+            div(class="pure-u-1 pure-u-md-1-3") {
+                div(style="margin: 5px;") {(panel(left))}
+            }
+            div(class="pure-u-1 pure-u-md-1-3") {
+                div(style="margin: 5px;") {
+                    table(class="pure-table", style="width: 100%;") {
+                        thead {
+                            tr {
+                                th {"Stat"}
+                                th {"Left"}
+                                th {"Δ"}
+                                th {"Right"}
+                            }
+                        }
+                        tbody {
+                            Indexed (
+                                iterable=rows,
+                                view=|cx, row| {
+                                    let better = row.better();
+                                    let class = move |side| if better == Some(side) { "mc-green" } else { "" };
+                                    let (left_class, right_class) = (class(Better::Left), class(Better::Right));
+                                    let show = |v: Option<f64>| v.map(format_value).unwrap_or_else(|| String::from("-"));
+                                    let delta = row.delta();
+                                    let delta = if delta > 0.0 {
+                                        format!("+{}", format_value(delta))
+                                    } else {
+                                        format_value(delta)
+                                    };
+
+                                    view! {cx,
+                                        tr {
+                                            td {(row.label)}
+                                            td(class=left_class) {(show(row.left))}
+                                            td {(delta)}
+                                            td(class=right_class) {(show(row.right))}
+                                        }
+                                    }
+                                }
+                            )
+                        }
+                    }
+                }
+            }
+            div(class="pure-u-1 pure-u-md-1-3") {
+                div(style="margin: 5px;") {(panel(right))}
+            }
+        }
+    }
+}
+
+/// Item, rolls and powders of one side of the comparison
+struct Side<'a> {
+    /// Name of the compared item, empty for the editor item
+    name: &'a Signal<String>,
+    /// Which roll of the item is shown
+    roll: &'a Signal<String>,
+    item: &'a ReadSignal<Option<Item>>,
+    ids: &'a ReadSignal<Vec<Id>>,
+    powders: &'a ReadSignal<Vec<RcSignal<Option<Powder>>>>,
+    /// Whether the editor roll was picked for an item other than the editor item
+    base_fallback: &'a ReadSignal<bool>,
+    /// Tooltip of the item
+    lines: &'a ReadSignal<Vec<Line>>,
+}
+
+fn side<'a>(cx: Scope<'a>, props: &CompareProps<'a>, roll: &str) -> &'a Side<'a> {
+    let item_list = props.item_list;
+    let registry = props.registry;
+    let (editor_item, editor_ids, editor_powders, editor_rerolls) = (
+        props.editor_item,
+        props.editor_ids,
+        props.editor_powders,
+        props.editor_rerolls,
+    );
+
+    let name = create_signal(cx, String::new());
+    let roll = create_signal(cx, roll.to_string());
+
+    let item = create_selector(cx, move || {
+        if name.get().is_empty() {
+            (*editor_item.get()).clone()
+        } else {
+            item_list
+                .items
+                .iter()
+                .find(|item| item.name.eq_ignore_ascii_case(&name.get()))
+                .cloned()
+        }
+    });
+
+    // the editor rolls are shared with the editor item even when it is searched by name
+    let editor_selected = create_selector(cx, move || match (&*item.get(), &*editor_item.get()) {
+        (Some(item), Some(editor)) => item.name == editor.name,
+        _ => false,
+    });
+    let uses_editor = create_selector(cx, move || {
+        *editor_selected.get() && *roll.get() == "editor"
+    });
+    let ids = create_selector(cx, move || {
+        if *uses_editor.get() {
+            return (*editor_ids.get()).clone();
+        }
+
+        match &*item.get() {
+            Some(item) => {
+                let ids = read_ids(item, registry);
+                roll_ids(&ids, &roll.get(), registry);
+                ids
+            }
+            None => Vec::new(),
+        }
+    });
+    // powders do not depend on the roll so the editor item keeps them with every roll
+    let powders = create_selector(cx, move || {
+        if *editor_selected.get() {
+            (*editor_powders.get()).clone()
+        } else {
+            Vec::new()
+        }
+    });
+    let rerolls = create_selector(cx, move || {
+        if *uses_editor.get() {
+            *editor_rerolls.get()
+        } else {
+            0
+        }
+    });
+
+    let base_fallback = create_selector(cx, move || {
+        item.get().is_some() && !*editor_selected.get() && *roll.get() == "editor"
+    });
+
+    // compared items are shown without the build and weight settings of the editor
+    let lines = create_tooltip(
+        cx,
+        item,
+        ids,
+        powders,
+        rerolls,
+        registry,
+        TooltipSettings {
+            skill_points: create_signal(cx, None),
+            sets: props.sets,
+            set_pieces: create_signal(cx, 1),
+            profile: create_signal(cx, None),
+            overall_mode: props.overall_mode,
+        },
+    );
+
+    create_ref(
+        cx,
+        Side {
+            name,
+            roll,
+            item,
+            ids,
+            powders,
+            base_fallback,
+            lines,
+        },
+    )
+}
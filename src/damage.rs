@@ -0,0 +1,68 @@
+//! Damage calculations for weapons
+
+use crate::{
+    powders::Powdered,
+    wynn::items::{AttackSpeed, DamageRange, Id},
+};
+
+/// Ids adding % damage to the earth, thunder, water, fire and air damage of a weapon
+const ELEMENT_BONUSES: [&str; 5] = [
+    "earthDamageBonus",
+    "thunderDamageBonus",
+    "waterDamageBonus",
+    "fireDamageBonus",
+    "airDamageBonus",
+];
+
+/// Average melee damage of a weapon
+#[derive(Clone, Copy, PartialEq)]
+pub struct AverageDamage {
+    pub per_hit: f64,
+    pub per_second: f64,
+    /// Attack speed after attack speed bonuses
+    pub speed: AttackSpeed,
+}
+
+/// Current value of an id or 0 if the item does not have the id
+pub fn id_value(ids: &[Id], key: &str) -> i32 {
+    ids.iter()
+        .find(|id| id.id.key() == key)
+        .map(|id| *id.value.get())
+        .unwrap_or(0)
+}
+
+/// Calculates the average melee damage of a weapon with the rolled damage ids.
+///
+/// The damages are in the neutral, earth, thunder, water, fire, air order.
+pub fn average_damage(
+    damages: &[Option<Powdered<DamageRange>>; 6],
+    speed: AttackSpeed,
+    ids: &[Id],
+) -> AverageDamage {
+    let bonus = id_value(ids, "damageBonus") as f64;
+    let raw = id_value(ids, "damageBonusRaw") as f64;
+
+    let mut per_hit = raw;
+
+    for (i, damage) in damages.iter().enumerate() {
+        if let Some(damage) = damage {
+            // the neutral damage is only affected by the general damage bonus
+            let element_bonus = if i == 0 {
+                0.0
+            } else {
+                id_value(ids, ELEMENT_BONUSES[i - 1]) as f64
+            };
+
+            per_hit += damage.value.average() * (1.0 + (bonus + element_bonus) / 100.0);
+        }
+    }
+
+    let per_hit = f64::max(per_hit, 0.0);
+    let speed = speed.shift(id_value(ids, "attackSpeedBonus"));
+
+    AverageDamage {
+        per_hit,
+        per_second: per_hit * speed.hits_per_second(),
+        speed,
+    }
+}
@@ -0,0 +1,314 @@
+//! Encoding and decoding of the values stored in wynntils id strings
+
+use std::fmt::Display;
+
+use crate::{
+    registry::Encoding,
+    wynn::items::{Id, Powders},
+};
+
+/// Offset for the wynntils id strings
+pub const OFFSET: i32 = 0xF5000;
+
+/// Largest number which can be stored in a single character.
+///
+/// Anything above this would collide with the characters used to mark the parts of the id string.
+pub const MAX_ENCODED: i32 = 0xFEF;
+
+/// Turns a number into the character representing it in the id string
+pub fn encode_char(n: i32) -> Option<char> {
+    if (0..=MAX_ENCODED).contains(&n) {
+        char::from_u32((OFFSET + n) as u32)
+    } else {
+        None
+    }
+}
+
+/// Encodes the value of an id into the number stored in the id string
+///
+/// Ids with a base value above 100 are stored as a rounded % of the base value and lose precision
+pub fn encode_value(id: &Id, value: i32) -> i32 {
+    if id.encoding() == Encoding::Percent {
+        (f64::round(value as f64 * 100.0 / id.baseval as f64) as i32 - 30) * 4
+    } else {
+        (value - id.min_id()) * 4
+    }
+}
+
+/// Decodes a number stored in an id string back into the value of the id the same way wynntils does
+pub fn decode_value(id: &Id, encoded: i32) -> i32 {
+    if id.encoding() == Encoding::Percent {
+        let percent = encoded / 4 + 30;
+        java_round(id.baseval as f64 * percent as f64 / 100.0)
+    } else {
+        encoded / 4 + id.min_id()
+    }
+}
+
+/// The value recipients of the id string will see for the given value
+pub fn represented_value(id: &Id, value: i32) -> i32 {
+    decode_value(id, encode_value(id, value))
+}
+
+/// Rounding the way java's `Math.round` does it which is what wynntils uses
+fn java_round(n: f64) -> i32 {
+    f64::floor(n + 0.5) as i32
+}
+
+/// Marks the start of an id string
+pub const START: char = '\u{F5FF0}';
+/// Marks the end of an id string
+pub const END: char = '\u{F5FF1}';
+/// Separates the name from the ids and the ids from the powders
+pub const SEPARATOR: char = '\u{F5FF2}';
+
+/// The raw contents of an id string
+pub struct DecodedString {
+    /// Name of the item
+    pub name: String,
+    /// Encoded numbers of the ids in the order they appear in
+    pub ids: Vec<i32>,
+    /// Powders applied to the item
+    pub powders: Vec<Powders>,
+    /// Amount of times the item has been rerolled
+    pub rerolls: i32,
+}
+
+/// Errors which can happen when decoding an id string
+pub enum DecodeError {
+    /// The string does not contain the start marker
+    MissingStart,
+    /// The string does not contain the end marker
+    MissingEnd,
+    /// The name is not followed by the separator
+    MissingSeparator,
+    /// There is no character for the rerolls
+    MissingRerolls,
+    /// A character which cannot appear in an id string
+    InvalidCharacter(char),
+    /// A powder which does not exist
+    InvalidPowder(i32),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::MissingStart => "The string does not contain the start of an item".fmt(f),
+            DecodeError::MissingEnd => "The string does not contain the end of the item".fmt(f),
+            DecodeError::MissingSeparator => "The item name is not followed by any ids".fmt(f),
+            DecodeError::MissingRerolls => "The string does not contain a reroll count".fmt(f),
+            DecodeError::InvalidCharacter(c) => {
+                format!("The string contains an invalid character U+{:X}", *c as u32).fmt(f)
+            }
+            DecodeError::InvalidPowder(n) => format!("The powder {n} does not exist").fmt(f),
+        }
+    }
+}
+
+/// Decodes an id string into the raw numbers stored in it
+///
+/// This does not need to know the item as the meaning of the numbers depends on it
+pub fn decode_string(s: &str) -> Result<DecodedString, DecodeError> {
+    let (_, s) = s.split_once(START).ok_or(DecodeError::MissingStart)?;
+    let (s, _) = s.split_once(END).ok_or(DecodeError::MissingEnd)?;
+    let (name, data) = s
+        .split_once(SEPARATOR)
+        .ok_or(DecodeError::MissingSeparator)?;
+
+    let mut data = data
+        .chars()
+        .map(|c| decode_char(c).map(|n| (c, n)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (_, rerolls) = data.pop().ok_or(DecodeError::MissingRerolls)?;
+
+    // powders come after a second separator
+    let (ids, powderchars) = match data.iter().position(|(c, _)| *c == SEPARATOR) {
+        Some(i) => (&data[..i], &data[i + 1..]),
+        None => (&data[..], &data[0..0]),
+    };
+
+    let mut powders = Vec::new();
+    for (_, n) in powderchars {
+        // each character holds up to 4 powders as base 6 digits
+        let mut digits = Vec::new();
+        let mut n = *n;
+        while n > 0 {
+            digits.push(n % 6);
+            n /= 6;
+        }
+
+        for digit in digits.into_iter().rev() {
+            powders.push(Powders::from_i32(digit - 1).ok_or(DecodeError::InvalidPowder(digit))?);
+        }
+    }
+
+    Ok(DecodedString {
+        name: name.to_string(),
+        ids: ids.iter().map(|(_, n)| *n).collect(),
+        powders,
+        rerolls,
+    })
+}
+
+/// Turns a character of an id string back into the number it represents
+fn decode_char(c: char) -> Result<i32, DecodeError> {
+    if c == SEPARATOR {
+        return Ok(0);
+    }
+
+    let n = c as i32 - OFFSET;
+    if (0..=MAX_ENCODED).contains(&n) {
+        Ok(n)
+    } else {
+        Err(DecodeError::InvalidCharacter(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sycamore::reactive::create_rc_signal;
+
+    use super::*;
+    use crate::wynn::items::{Identification, StatusType};
+
+    fn id(baseval: i32) -> Id {
+        Id {
+            id: Identification::new("spellDamage"),
+            idtype: StatusType::PERCENTAGE,
+            fixed: false,
+            baseval,
+            value: create_rc_signal(baseval),
+        }
+    }
+
+    /// Builds an id string out of already encoded numbers
+    fn id_string(name: &str, ids: &[i32], powders: &[i32], rerolls: i32) -> String {
+        let mut s = format!("{START}{name}{SEPARATOR}");
+        s.extend(ids.iter().map(|n| encode_char(*n).unwrap()));
+        if !powders.is_empty() {
+            s.push(SEPARATOR);
+            s.extend(powders.iter().map(|n| encode_char(*n).unwrap()));
+        }
+        s.push(encode_char(rerolls).unwrap());
+        s.push(END);
+        s
+    }
+
+    #[test]
+    fn value_round_trip() {
+        for baseval in [2, 20, 100, -2, -20, -100] {
+            let id = id(baseval);
+            assert!(id.encoding() == Encoding::Value);
+            for value in id.min_id().min(id.max_id())..=id.max_id().max(id.min_id()) {
+                let encoded = encode_value(&id, value);
+                assert!(encoded >= 0 && encoded % 4 == 0);
+                assert_eq!(decode_value(&id, encoded), value, "base {baseval}");
+            }
+        }
+    }
+
+    #[test]
+    fn percent_round_trip() {
+        let id = id(500);
+        assert!(id.encoding() == Encoding::Percent);
+
+        // the ends of the range survive
+        assert_eq!(represented_value(&id, id.min_id()), 150);
+        assert_eq!(represented_value(&id, id.max_id()), 650);
+        assert_eq!(encode_value(&id, 650), 400);
+        // values between whole % are rounded
+        assert_eq!(represented_value(&id, 651), 650);
+        assert_eq!(represented_value(&id, 653), 655);
+    }
+
+    #[test]
+    fn negative_percent_round_trip() {
+        let id = id(-200);
+        assert!(id.encoding() == Encoding::Percent);
+
+        assert_eq!(represented_value(&id, -260), -260);
+        assert_eq!(represented_value(&id, -140), -140);
+        assert_eq!(represented_value(&id, -201), -202);
+    }
+
+    #[test]
+    fn java_rounding() {
+        assert_eq!(java_round(0.5), 1);
+        assert_eq!(java_round(2.5), 3);
+        assert_eq!(java_round(-0.5), 0);
+        assert_eq!(java_round(-2.5), -2);
+        assert_eq!(java_round(-2.6), -3);
+
+        // 30% of the base value lands on a half
+        assert_eq!(decode_value(&id(105), 0), 32);
+        assert_eq!(decode_value(&id(-105), 0), -31);
+    }
+
+    #[test]
+    fn char_boundaries() {
+        assert_eq!(encode_char(-1), None);
+        assert_eq!(encode_char(MAX_ENCODED + 1), None);
+
+        for n in [0, 1, MAX_ENCODED] {
+            let c = encode_char(n).unwrap();
+            assert!(matches!(decode_char(c), Ok(m) if m == n));
+        }
+
+        // the markers come right after the largest number
+        assert!(matches!(
+            decode_char(START),
+            Err(DecodeError::InvalidCharacter(START))
+        ));
+        assert!(matches!(
+            decode_char(END),
+            Err(DecodeError::InvalidCharacter(END))
+        ));
+    }
+
+    #[test]
+    fn string_round_trip() {
+        // fire then water packed into one character
+        let powders = Powders::FIRE.to_i32() * 6 + Powders::WATER.to_i32();
+        let s = id_string("Test Wand", &[0, 52, MAX_ENCODED], &[powders], 3);
+
+        let Ok(decoded) = decode_string(&format!("prefix {s} suffix")) else {
+            panic!("decoding failed");
+        };
+        assert_eq!(decoded.name, "Test Wand");
+        assert_eq!(decoded.ids, vec![0, 52, MAX_ENCODED]);
+        assert!(decoded.powders == vec![Powders::FIRE, Powders::WATER]);
+        assert_eq!(decoded.rerolls, 3);
+    }
+
+    #[test]
+    fn malformed_strings() {
+        let reroll = encode_char(0).unwrap();
+
+        assert!(matches!(
+            decode_string("Test Wand"),
+            Err(DecodeError::MissingStart)
+        ));
+        assert!(matches!(
+            decode_string(&format!("{START}Test Wand{SEPARATOR}{reroll}")),
+            Err(DecodeError::MissingEnd)
+        ));
+        assert!(matches!(
+            decode_string(&format!("{START}Test Wand{reroll}{END}")),
+            Err(DecodeError::MissingSeparator)
+        ));
+        assert!(matches!(
+            decode_string(&format!("{START}Test Wand{SEPARATOR}{END}")),
+            Err(DecodeError::MissingRerolls)
+        ));
+        assert!(matches!(
+            decode_string(&format!("{START}Test Wand{SEPARATOR}a{reroll}{END}")),
+            Err(DecodeError::InvalidCharacter('a'))
+        ));
+        // a zero digit is not a powder
+        assert!(matches!(
+            decode_string(&id_string("Test Wand", &[0], &[6], 0)),
+            Err(DecodeError::InvalidPowder(0))
+        ));
+    }
+}
@@ -0,0 +1,170 @@
+use sycamore::prelude::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Blob, HtmlAnchorElement, Url};
+
+use crate::{
+    copy_to_clipboard,
+    minecraft::{give_command, render_json, render_legacy},
+    png::{render_png, MAX_SCALE, MIN_SCALE},
+    registry::IdRegistry,
+    sets::find_set,
+    svg::render_svg,
+    tooltip::{tooltip, Line, OverallMode, TooltipInput},
+    weights::WeightProfile,
+    wynn::items::{Id, Item, ItemSet, Powder},
+};
+
+#[derive(Prop)]
+pub struct ExportProps<'a> {
+    item: &'a ReadSignal<Option<Item>>,
+    /// Tooltip lines as shown in the preview
+    lines: &'a ReadSignal<Vec<Line>>,
+}
+
+/// Page settings which change what the tooltip shows
+#[derive(Clone, Copy)]
+pub struct TooltipSettings<'a> {
+    /// Skill points assumed for the damage and health calculations
+    pub skill_points: &'a ReadSignal<Option<[i32; 5]>>,
+    /// All known item sets
+    pub sets: &'a [ItemSet],
+    /// Amount of set pieces assumed to be equipped
+    pub set_pieces: &'a ReadSignal<i32>,
+    /// Weights to score the rolls with
+    pub profile: &'a ReadSignal<Option<WeightProfile>>,
+    /// How the overall % in the title is calculated
+    pub overall_mode: &'a ReadSignal<OverallMode>,
+}
+
+/// Tooltip lines of the item which update with the rolls, powders and settings
+pub fn create_tooltip<'a>(
+    cx: Scope<'a>,
+    item: &'a ReadSignal<Option<Item>>,
+    ids: &'a ReadSignal<Vec<Id>>,
+    powders: &'a ReadSignal<Vec<RcSignal<Option<Powder>>>>,
+    rerolls: &'a ReadSignal<i32>,
+    registry: &'a IdRegistry,
+    settings: TooltipSettings<'a>,
+) -> &'a ReadSignal<Vec<Line>> {
+    create_selector(cx, move || {
+        let item = item.get();
+        let Some(item) = item.as_ref() else {
+            return Vec::new();
+        };
+        let powders = powders.get().iter().map(|p| *p.get()).collect::<Vec<_>>();
+        let profile = settings.profile.get();
+
+        tooltip(&TooltipInput {
+            item,
+            ids: &ids.get(),
+            powders: &powders,
+            rerolls: *rerolls.get(),
+            registry,
+            overall_mode: *settings.overall_mode.get(),
+            skill_points: *settings.skill_points.get(),
+            profile: profile.as_ref().as_ref(),
+            set: find_set(item, settings.sets),
+            set_pieces: *settings.set_pieces.get(),
+        })
+    })
+}
+
+/// Saves data to a file by clicking a link to a temporary blob url
+fn download(data: &[u8], file_name: &str) -> Option<()> {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
+    let blob = Blob::new_with_u8_array_sequence(&parts).ok()?;
+    let url = Url::create_object_url_with_blob(&blob).ok()?;
+
+    let window = web_sys::window()?;
+    let document = window.document()?;
+    let link = document
+        .create_element("a")
+        .ok()?
+        .dyn_into::<HtmlAnchorElement>()
+        .ok()?;
+    link.set_href(&url);
+    link.set_download(file_name);
+
+    // some browsers only follow links which are part of the page
+    document.body()?.append_child(&link).ok()?;
+    link.click();
+    link.remove();
+
+    // revoking the url straight away can cancel the download before it starts
+    let revoke = Closure::once_into_js(move || {
+        let _ = Url::revoke_object_url(&url);
+    });
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), 0)
+        .ok()?;
+    Some(())
+}
+
+/// Downloads and copies of the tooltip of the item
+#[component]
+pub fn Export<'a, G: Html>(cx: Scope<'a>, props: ExportProps<'a>) -> View<G> {
+    let lines = props.lines;
+
+    let name = create_selector(cx, move || {
+        props
+            .item
+            .get()
+            .as_ref()
+            .as_ref()
+            .map(|i| i.name.clone())
+            .unwrap_or_default()
+    });
+
+    // the files are only rendered when they are downloaded
+    let scale = create_signal(cx, String::from("2"));
+    let download_svg = move |_| {
+        download(
+            render_svg(&lines.get()).as_bytes(),
+            &format!("{}.svg", name.get()),
+        );
+    };
+    let download_png = move |_| {
+        let scale = scale.get().parse().unwrap_or(MIN_SCALE);
+        download(
+            &render_png(&lines.get(), scale),
+            &format!("{}.png", name.get()),
+        );
+    };
+
+    let scales = View::new_fragment(
+        (MIN_SCALE..=MAX_SCALE)
+            .map(|s| view! {cx, option(value=s.to_string()) {(format!("{s}x"))}})
+            .collect(),
+    );
+
+    let give_error = create_signal(cx, None::<String>);
+    let give = move |_| {
+        if let Some(item) = props.item.get().as_ref() {
+            match give_command(item, &lines.get()) {
+                Ok(command) => {
+                    give_error.set(None);
+                    copy_to_clipboard(&command)
+                }
+                Err(e) => give_error.set(Some(e.to_string())),
+            }
+        }
+    };
+
+    view! {cx,
+        div(style="display: flex; gap: 5px; margin-top: 5px;") {
+            button(class="pure-button", style="flex-grow: 1;", on:click=download_svg) {"Download SVG"}
+            button(class="pure-button", style="flex-grow: 1;", on:click=download_png) {"Download PNG"}
+            form(class="pure-form") {
+                select(bind:value=scale) {(scales)}
+            }
+        }
+        div(style="display: flex; gap: 5px; margin-top: 5px;") {
+            button(class="pure-button", style="flex-grow: 1;", on:click=give) {"Copy /give"}
+            button(class="pure-button", style="flex-grow: 1;", on:click=move |_| copy_to_clipboard(&render_json(&lines.get()))) {"Copy JSON"}
+            button(class="pure-button", style="flex-grow: 1;", on:click=move |_| copy_to_clipboard(&render_legacy(&lines.get()).join("\n"))) {"Copy § codes"}
+        }
+        (if let Some(e) = (*give_error.get()).clone() {
+            view! {cx, p(class="problem mc-red") {(e)}}
+        } else {view!{cx,}})
+    }
+}
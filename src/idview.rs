@@ -1,18 +1,26 @@
 use sycamore::prelude::*;
 
-use crate::wynn::items::{Id, Item, Powders};
+use crate::{
+    copy_to_clipboard,
+    encoding::{encode_char, encode_value, END, SEPARATOR, START},
+    registry::{Encoding, IdRegistry},
+    text::{render_ansi, render_markdown},
+    tooltip::Line,
+    validation::{validate, Severity},
+    wynn::items::{Id, Item, Powder},
+};
 
 #[derive(Prop)]
 pub struct IdViewProps<'a> {
     item: &'a ReadSignal<Option<Item>>,
     ids: &'a ReadSignal<Vec<Id>>,
-    powders: &'a ReadSignal<Vec<RcSignal<Option<Powders>>>>,
+    powders: &'a ReadSignal<Vec<RcSignal<Option<Powder>>>>,
     rerolls: &'a ReadSignal<i32>,
+    registry: &'a IdRegistry,
+    /// Tooltip lines as shown in the preview
+    lines: &'a ReadSignal<Vec<Line>>,
 }
 
-/// Offset for the wynntils id strings
-const OFFSET: i32 = 0xF5000;
-
 #[component]
 pub fn IdView<'a, G: Html>(cx: Scope<'a>, props: IdViewProps<'a>) -> View<G> {
     // create the id string
@@ -21,32 +29,13 @@ pub fn IdView<'a, G: Html>(cx: Scope<'a>, props: IdViewProps<'a>) -> View<G> {
 
         // encode the ids
         for id in props.ids.get().iter() {
-            // check if the id is fixed
-            if !(id.fixed || -1 <= id.baseval && id.baseval <= 1) {
-                // check if we will save a value or a %
-                if i32::abs(id.baseval) > 100 {
-                    // calculate the %
-                    idstr.push(
-                        char::from_u32(
-                            (OFFSET
-                                + (f64::round(*id.value.get() as f64 * 100.0 / id.baseval as f64)
-                                    as i32
-                                    - 30)
-                                    * 4) as u32,
-                        )
-                        .unwrap_or('?'),
-                    );
-                } else {
-                    // just push the value
-                    idstr.push(
-                        char::from_u32((OFFSET + (*id.value.get() - id.min_id()) * 4) as u32)
-                            .unwrap_or('?'),
-                    );
-                }
-            } else {
+            match id.encoding() {
+                // fixed ids are not stored
+                Encoding::Fixed => {}
                 // I have no idea why this is here
-                if !id.fixed {
-                    idstr.push(char::from_u32(OFFSET as u32).unwrap_or('?'));
+                Encoding::Constant => idstr.push(encode_char(0).unwrap_or('?')),
+                Encoding::Value | Encoding::Percent => {
+                    idstr.push(encode_char(encode_value(id, *id.value.get())).unwrap_or('?'))
                 }
             }
         }
@@ -58,27 +47,27 @@ pub fn IdView<'a, G: Html>(cx: Scope<'a>, props: IdViewProps<'a>) -> View<G> {
 
         for powder in props.powders.get().iter() {
             if appended == 4 {
-                powders.push(char::from_u32(OFFSET as u32 + currpowder).unwrap_or('?'));
+                powders.push(encode_char(currpowder).unwrap_or('?'));
                 appended = 0;
                 currpowder = 0;
             }
 
             if let Some(powder) = *powder.get() {
                 currpowder *= 6;
-                currpowder += powder.to_i32() as u32;
+                currpowder += powder.element.to_i32();
                 appended += 1;
             }
         }
         if currpowder != 0 {
-            powders.push(char::from_u32(OFFSET as u32 + currpowder).unwrap_or('?'));
+            powders.push(encode_char(currpowder).unwrap_or('?'));
         }
         if !powders.is_empty() {
-            idstr.push(char::from_u32(0xF5FF2).unwrap());
+            idstr.push(SEPARATOR);
             idstr.push_str(&powders);
         }
 
         // add the rerolls
-        idstr.push(char::from_u32((OFFSET + *props.rerolls.get()) as u32).unwrap_or('?'));
+        idstr.push(encode_char(*props.rerolls.get()).unwrap_or('?'));
 
         let name = if let Some(item) = &*props.item.get() {
             item.name.clone()
@@ -87,22 +76,61 @@ pub fn IdView<'a, G: Html>(cx: Scope<'a>, props: IdViewProps<'a>) -> View<G> {
         };
 
         // return the id string
-        format!("󵿰{}󵿲{}󵿱", name, idstr)
+        format!("{START}{name}{SEPARATOR}{idstr}{END}")
+    });
+
+    // check the values before letting the string be copied
+    let problems = create_selector(cx, || {
+        if let Some(item) = &*props.item.get() {
+            validate(item, &props.ids.get(), props.registry, *props.rerolls.get())
+        } else {
+            Vec::new()
+        }
+    });
+
+    let has_errors = create_selector(cx, || {
+        problems.get().iter().any(|p| p.severity == Severity::Error)
     });
 
+    // the tooltip for pasting into discord or forums
+    let lines = props.lines;
+
     view! {cx,
         div {
             code {(idstring.get())}
 
+            Indexed (
+                iterable=problems,
+                view=|cx, problem| {
+                    let class = match problem.severity {
+                        Severity::Warning => "problem mc-gold",
+                        Severity::Error => "problem mc-red",
+                    };
+
+                    view! {cx,
+                        p(class=class) {(problem.message)}
+                    }
+                }
+            )
+
             button(
                 class="pure-button pure-button-primary",
                 style="width:100%",
-                on:click= move |_| {
-                    let clip = web_sys::window().unwrap().navigator().clipboard().unwrap();
-
-                    let _ = clip.write_text(&idstring.get());
-                }
+                disabled=*has_errors.get(),
+                on:click=move |_| copy_to_clipboard(&idstring.get())
             ) {"Copy to clipboard"}
+            div(style="display: flex; gap: 5px; margin-top: 5px;") {
+                button(
+                    class="pure-button",
+                    style="flex-grow: 1;",
+                    on:click=move |_| copy_to_clipboard(&render_ansi(&lines.get()))
+                ) {"Copy for Discord"}
+                button(
+                    class="pure-button",
+                    style="flex-grow: 1;",
+                    on:click=move |_| copy_to_clipboard(&render_markdown(&lines.get()))
+                ) {"Copy as Markdown"}
+            }
         }
     }
 }
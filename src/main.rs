@@ -1,38 +1,89 @@
 use reqwasm::http::Request;
 use sycamore::prelude::*;
 use sycamore::suspense::Suspense;
-use wynn::items::{Id, IdentificationOrder, Item, ItemList};
-
-use crate::{idview::IdView, itemrender::ItemRender, slider::Slider, wynn::items::Powders};
-
+use wynn::items::{Id, Item, ItemList};
+
+use crate::{
+    build::BuildWorkspace,
+    checker::Checker,
+    compare::Compare,
+    encoding::represented_value,
+    export::{create_tooltip, Export, TooltipSettings},
+    idview::IdView,
+    itemrender::ItemRender,
+    optimizer::Optimizer,
+    powderselect::PowderSelect,
+    recommend::Recommendations,
+    registry::{base_name, IdRegistry},
+    sets::{find_set, load_sets},
+    slider::Slider,
+    tooltip::OverallMode,
+    weights::{load_profiles, WeightEditor},
+    wynn::items::Powder,
+};
+
+mod build;
+mod checker;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+mod compare;
+mod damage;
+mod encoding;
+mod export;
+mod font;
 mod idview;
 mod itemrender;
+mod minecraft;
+mod optimizer;
+mod png;
+mod powders;
+mod powderselect;
+mod recommend;
+mod registry;
+mod sets;
+mod skillpoints;
 mod slider;
+mod solver;
+mod svg;
+#[cfg(not(target_arch = "wasm32"))]
+mod terminal;
+mod text;
+mod tooltip;
+mod validation;
+mod weights;
 mod wynn;
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 fn main() {
-    sycamore::render(|cx| {
-        view! { cx,
-            div(class="pure-g") {
-                Suspense(fallback = view! {cx,
-                    div(class="pure-u-1 pure-u-sm-1-2 pure-u-md-1-3 pure-u-lg-1-4") {
-                        div(class="box") {
-                            h1(class="title") {"idMangler"}
-                        }
+    // outside the browser the item list is read from a file instead
+    #[cfg(not(target_arch = "wasm32"))]
+    cli::run();
+    #[cfg(target_arch = "wasm32")]
+    sycamore::render(page);
+}
+
+/// The whole page, also compiled outside the browser so native builds check it
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn page<G: Html>(cx: Scope) -> View<G> {
+    view! { cx,
+        div(class="pure-g") {
+            Suspense(fallback = view! {cx,
+                div(class="pure-u-1 pure-u-sm-1-2 pure-u-md-1-3 pure-u-lg-1-4") {
+                    div(class="box") {
+                        h1(class="title") {"idMangler"}
                     }
-                }) {
-                    App {}
                 }
+            }) {
+                App {}
             }
-            div(style="position: fixed; bottom: 0; right: 0; z-index: -999; text-align: right") {
-                h4(style="margin: 5px; color: #aaa") {
-                    a(href="https://github.com/Zatzou/idmangler", style="margin: 5px; color: #aaa"){(format!("idMangler v{}", VERSION))}
-                }
+        }
+        div(style="position: fixed; bottom: 0; right: 0; z-index: -999; text-align: right") {
+            h4(style="margin: 5px; color: #aaa") {
+                a(href="https://github.com/Zatzou/idmangler", style="margin: 5px; color: #aaa"){(format!("idMangler v{}", VERSION))}
             }
         }
-    });
+    }
 }
 
 #[component]
@@ -73,7 +124,13 @@ async fn App<G: Html>(cx: Scope<'_>) -> View<G> {
 
     // unwrap is safe here
     // we also leak the itemlist to make it live for 'static
-    let item_list = Box::leak(Box::new(item_list.unwrap()));
+    let item_list: &ItemList = Box::leak(Box::new(item_list.unwrap()));
+
+    // everything known about the ids
+    let registry = create_ref(cx, IdRegistry::new(item_list));
+
+    // set pieces and bonuses
+    let sets = create_ref(cx, load_sets(item_list));
 
     let itemnames = View::new_fragment(
         item_list
@@ -106,7 +163,7 @@ async fn App<G: Html>(cx: Scope<'_>) -> View<G> {
     // the identifications of the currently selected item
     let selected_item_ids = create_selector(cx, || {
         if let Some(item) = &*selected_item.get() {
-            read_ids(item, &item_list.order)
+            read_ids(item, registry)
         } else {
             Vec::new()
         }
@@ -123,11 +180,64 @@ async fn App<G: Html>(cx: Scope<'_>) -> View<G> {
 
             powders
         } else {
-            Vec::<RcSignal<Option<Powders>>>::new()
+            Vec::<RcSignal<Option<Powder>>>::new()
+        }
+    });
+
+    // whether id values should be snapped to values which can be represented in the id string
+    let snap = create_signal(cx, false);
+
+    // amount of set pieces equipped in the preview
+    let set_pieces = create_ref(cx, create_rc_signal(1));
+
+    // weight profiles for scoring rolls
+    let profiles = create_signal(cx, load_profiles());
+    let selected_profile = create_signal(cx, String::new());
+    let active_profile = create_selector(cx, || {
+        profiles
+            .get()
+            .iter()
+            .find(|p| p.name == *selected_profile.get())
+            .cloned()
+    });
+
+    // how the overall % of the preview is calculated
+    let range_weighted = create_signal(cx, false);
+    let overall_mode = create_selector(cx, || {
+        if *range_weighted.get() {
+            OverallMode::RangeWeighted
+        } else {
+            OverallMode::Average
+        }
+    });
+
+    // skill points assumed in the preview
+    let assume_skill_points = create_signal(cx, false);
+    let skill_points = create_ref(cx, [(); 5].map(|_| create_rc_signal(0)));
+    let assumed_skill_points = create_selector(cx, || {
+        if *assume_skill_points.get() {
+            Some(skill_points.each_ref().map(|p| *p.get()))
+        } else {
+            None
         }
     });
 
-    let ordering = &item_list.order;
+    // the tooltip shared by the preview, the exports and the copied text
+    let tooltip_lines = create_tooltip(
+        cx,
+        selected_item,
+        selected_item_ids,
+        selected_item_powders,
+        rerolls,
+        registry,
+        TooltipSettings {
+            skill_points: assumed_skill_points,
+            sets,
+            set_pieces,
+            profile: active_profile,
+            overall_mode,
+        },
+    );
 
     view! {cx,
         // main search box
@@ -150,60 +260,99 @@ async fn App<G: Html>(cx: Scope<'_>) -> View<G> {
                 view! {cx,
                     div(class="box") {
                         h3 {"ID values"}
+                        form(class="pure-form") {
+                            label {
+                                input(prop:type="checkbox", bind:checked=snap)
+                                " Snap to values recipients can see"
+                            }
+                        }
                         Keyed (
                             iterable=selected_item_ids,
-                            view=|cx, id| {
+                            view=move |cx, id| {
                                 // don't render if the id is fixed
-                                if id.fixed || (-1 <= id.baseval && id.baseval <= 1) {
+                                if id.encoding().is_fixed() {
                                     return view! {cx,}
                                 }
 
+                                let id = create_ref(cx, id);
+                                let name = match &*selected_item.get_untracked() {
+                                    Some(item) => registry.name(&id.id, &item.item_info.r#type),
+                                    None => base_name(&id.id),
+                                };
+
+                                // the value wynntils will decode from the id string
+                                let represented = create_selector(cx, || represented_value(id, *id.value.get()));
+
+                                // snap the value if enabled, values out of range are left alone as they
+                                // may be half typed
+                                create_effect(cx, || {
+                                    let value = *id.value.get();
+                                    if *snap.get()
+                                        && (id.min_id()..=id.max_id()).contains(&value)
+                                        && *represented.get() != value
+                                    {
+                                        id.value.set(*represented.get());
+                                    }
+                                });
+
                                 view! {cx,
-                                    Slider(name=id.id.to_string(), min=id.min_id(), max=id.max_id(), value=id.value)
+                                    Slider(name=name, min=id.min_id(), max=id.max_id(), value=id.value.clone())
+                                    (if *represented.get() != *id.value.get() {
+                                        view! {cx,
+                                            p(class="lossy") {(format!("You set {}, recipients will see {}", id.value.get(), represented.get()))}
+                                        }
+                                    } else {view!{cx,}})
                                 }
                             },
                             key=|id| id.baseval
                         )
 
                         // powder editor
-                        (if selected_item_powders.get().len() != 0 {
+                        (if !selected_item_powders.get().is_empty() {
                             view!{cx,
                                 br {}
                                 h3 {"Powders"}
                                 div(style="display: flex; width: 100%; flex-wrap: wrap; justify-content: space-between; gap: 10px;") {
                                     Indexed (
                                         iterable=selected_item_powders,
-                                        view=|cx, powder| {
-                                            let powderval = if let Some(pow) = *powder.get() {
-                                                pow.to_i32()
-                                            } else { 0 };
-                                            let value = create_signal(cx, powderval.to_string());
-
-                                            let changeval = move |_| {
-                                                powder.set(Powders::from_i32(value.get().parse().unwrap_or_default()))
-                                            };
-
-                                            view!(cx,
-                                                form(class="pure-form", style="flex-grow: 1;") {
-                                                    select(
-                                                        style="width: 100%;",
-                                                        on:change=changeval,
-                                                        bind:value=value
-                                                    ) {
-                                                        option(value="6") {"None"}
-                                                        option(class="mc-dark-green", value="0") {"Earth"}
-                                                        option(class="mc-gold", value="1") {"Thunder"}
-                                                        option(class="mc-blue", value="2") {"Water"}
-                                                        option(class="mc-red", value="3") {"Fire"}
-                                                        option(value="4") {"Air"}
-                                                    }
-                                                }
-                                            )
-                                        }
+                                        view=|cx, powder| view! {cx, PowderSelect(powder=powder)}
                                     )
                                 }
                             }
                         } else {view!{cx,}})
+
+                        // equipped set pieces
+                        (if let Some(set) = selected_item.get().iter().find_map(|item| find_set(item, sets)) {
+                            view! {cx,
+                                br {}
+                                h3 {"Set"}
+                                Slider(name=String::from("Pieces equipped"), min=1, max=set.items.len() as i32, value=set_pieces.clone())
+                            }
+                        } else {view!{cx,}})
+
+                        // weight profile
+                        br {}
+                        h3 {"Weights"}
+                        WeightEditor(registry=registry, profiles=profiles, selected=selected_profile)
+
+                        // assumed skill points
+                        br {}
+                        h3 {"Skill points"}
+                        form(class="pure-form") {
+                            label {
+                                input(prop:type="checkbox", bind:checked=assume_skill_points)
+                                " Assume skill points"
+                            }
+                        }
+                        (if *assume_skill_points.get() {
+                            view! {cx,
+                                Slider(name=String::from("Strength"), min=0, max=150, value=skill_points[0].clone())
+                                Slider(name=String::from("Dexterity"), min=0, max=150, value=skill_points[1].clone())
+                                Slider(name=String::from("Intelligence"), min=0, max=150, value=skill_points[2].clone())
+                                Slider(name=String::from("Defence"), min=0, max=150, value=skill_points[3].clone())
+                                Slider(name=String::from("Agility"), min=0, max=150, value=skill_points[4].clone())
+                            }
+                        } else {view!{cx,}})
                     }
                 }
             } else {view!{cx,}})
@@ -215,7 +364,7 @@ async fn App<G: Html>(cx: Scope<'_>) -> View<G> {
                 view! {cx,
                     div(class="box") {
                         h3 {"Result"}
-                        IdView(item=selected_item, ids=selected_item_ids, powders=selected_item_powders, rerolls=rerolls)
+                        IdView(item=selected_item, ids=selected_item_ids, powders=selected_item_powders, rerolls=rerolls, registry=registry, lines=tooltip_lines)
                         br {}
                         Slider(name=String::from("Rolls"), min=1, max=1000, value=rerolls.clone())
                     }
@@ -229,23 +378,73 @@ async fn App<G: Html>(cx: Scope<'_>) -> View<G> {
                 view! {cx,
                     div(class="box") {
                         h3 {"Preview"}
-                        ItemRender(item=selected_item, ids=selected_item_ids, powders=selected_item_powders, rerolls=rerolls, ordering=ordering)
+                        form(class="pure-form") {
+                            label {
+                                input(prop:type="checkbox", bind:checked=range_weighted)
+                                " Weight overall % by roll range"
+                            }
+                        }
+                        ItemRender(lines=tooltip_lines)
+                        Export(item=selected_item, lines=tooltip_lines)
                     }
                 }
             } else {view!{cx,}})
         }
+
+        // item string checker
+        div(class="pure-u-1 pure-u-sm-1-2 pure-u-md-1-3 pure-u-lg-1-4") {
+            div(class="box") {
+                h3 {"Check an item string"}
+                Checker(item_list=item_list, registry=registry)
+            }
+        }
+
+        // comparison
+        div(class="pure-u-1") {
+            div(class="box") {
+                h3 {"Compare"}
+                Compare(item_list=item_list, registry=registry, sets=sets, editor_item=selected_item, editor_ids=selected_item_ids, editor_powders=selected_item_powders, editor_rerolls=rerolls, overall_mode=overall_mode)
+            }
+        }
+
+        // item recommendations
+        div(class="pure-u-1") {
+            div(class="box") {
+                h3 {"Recommended items"}
+                Recommendations(item_list=item_list, registry=registry, profile=active_profile, searchtext=searchtext)
+            }
+        }
+
+        // build workspace
+        div(class="pure-u-1") {
+            div(class="box") {
+                h3 {"Build"}
+                BuildWorkspace(item_list=item_list, registry=registry)
+            }
+        }
+
+        // build optimizer
+        div(class="pure-u-1") {
+            div(class="box") {
+                h3 {"Build optimizer"}
+                Optimizer(item_list=item_list, registry=registry, profile=active_profile)
+            }
+        }
+    }
+}
+
+/// Copies text to the clipboard if the browser has one
+fn copy_to_clipboard(text: &str) {
+    if let Some(clip) = web_sys::window().and_then(|w| w.navigator().clipboard()) {
+        let _ = clip.write_text(text);
     }
 }
 
 /// function to read ids into a btreemap from an item
-fn read_ids(item: &Item, ord: &IdentificationOrder) -> Vec<Id> {
+fn read_ids(item: &Item, registry: &IdRegistry) -> Vec<Id> {
     let mut finalids = Vec::new();
 
-    // ensure sorting
-    let mut ordering = ord.order.iter().collect::<Vec<_>>();
-    ordering.sort_by(|a, b| a.1.cmp(b.1));
-
-    for (id, _) in ordering {
+    for id in registry.sorted() {
         if let Some(sid) = item.statuses.get(id) {
             finalids.push(Id {
                 id: id.clone(),
@@ -0,0 +1,221 @@
+//! Exports of the item tooltip for recreating items in minecraft
+
+use std::fmt::Display;
+
+use serde_json::{json, Value};
+
+use crate::{
+    tooltip::{rarity_color, Line, Role},
+    wynn::items::{Item, Type},
+};
+
+/// A line as a text component.
+///
+/// Italics are turned off explicitly since lore is italic by default.
+fn component(line: &Line) -> Value {
+    let extra = line
+        .spans
+        .iter()
+        .map(|span| {
+            json!({
+                "text": span.text,
+                "color": span.color.name(),
+                "italic": span.italic,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if extra.is_empty() {
+        json!({ "text": "" })
+    } else {
+        json!({ "text": "", "italic": false, "extra": extra })
+    }
+}
+
+/// The lines the item would show in game.
+///
+/// Lines worked out by the page and roll % are left out.
+fn game_lines(lines: &[Line]) -> Vec<Line> {
+    let mut out: Vec<Line> = Vec::new();
+    for line in lines.iter().filter(|line| !line.computed) {
+        let line = Line {
+            spans: line
+                .spans
+                .iter()
+                .filter(|span| span.role != Role::Percent)
+                .cloned()
+                .collect(),
+            computed: false,
+        };
+        // dropped sections leave their separators behind
+        if line.spans.is_empty() && out.last().is_some_and(|l| l.spans.is_empty()) {
+            continue;
+        }
+        out.push(line);
+    }
+    out
+}
+
+/// The tooltip as a json array of text components
+pub fn render_json(lines: &[Line]) -> String {
+    Value::Array(game_lines(lines).iter().map(component).collect()).to_string()
+}
+
+/// The tooltip with legacy `§` formatting codes, one string per line
+pub fn render_legacy(lines: &[Line]) -> Vec<String> {
+    game_lines(lines)
+        .iter()
+        .map(|line| {
+            let mut out = String::new();
+            for span in &line.spans {
+                out.push('§');
+                out.push(span.color.code());
+                if span.italic {
+                    out.push_str("§o");
+                }
+                out.push_str(&span.text);
+            }
+            out
+        })
+        .collect()
+}
+
+/// The closest vanilla item to an item type
+fn base_item(r#type: &Type) -> &'static str {
+    match r#type {
+        Type::SPEAR => "minecraft:iron_shovel",
+        Type::WAND => "minecraft:stick",
+        Type::BOW => "minecraft:bow",
+        Type::DAGGER => "minecraft:shears",
+        Type::RELIK => "minecraft:golden_shovel",
+        Type::HELMET => "minecraft:leather_helmet",
+        Type::CHESTPLATE => "minecraft:leather_chestplate",
+        Type::LEGGINGS => "minecraft:leather_leggings",
+        Type::BOOTS => "minecraft:leather_boots",
+        Type::RING => "minecraft:gold_nugget",
+        Type::BRACELET => "minecraft:chain",
+        Type::NECKLACE => "minecraft:string",
+        Type::INVALID => "minecraft:paper",
+    }
+}
+
+/// Quotes json for use inside an snbt string
+fn quote(value: &Value) -> String {
+    format!(
+        "'{}'",
+        value.to_string().replace('\\', "\\\\").replace('\'', "\\'")
+    )
+}
+
+/// Longest command minecraft accepts
+pub const MAX_COMMAND_LENGTH: usize = 32767;
+
+/// Errors which can happen when building a `/give` command
+pub enum GiveError {
+    /// The command is longer than minecraft accepts
+    TooLong(usize),
+}
+
+impl Display for GiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GiveError::TooLong(len) => write!(
+                f,
+                "The /give command is {len} characters long, over the limit of {MAX_COMMAND_LENGTH}"
+            ),
+        }
+    }
+}
+
+/// A `/give` command for an item named after it with the rest of the tooltip as its lore.
+///
+/// The attributes of the vanilla item are removed so it does not list its own damage or armor.
+pub fn give_command(item: &Item, lines: &[Line]) -> Result<String, GiveError> {
+    let name = json!({
+        "text": item.name,
+        "color": rarity_color(&item.tier).name(),
+        "italic": false,
+    });
+
+    let lore = game_lines(lines)
+        .iter()
+        .skip(1)
+        .map(|line| quote(&component(line)))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let command = format!(
+        "/give @p {}[custom_name={},lore=[{lore}],attribute_modifiers=[]]",
+        base_item(&item.item_info.r#type),
+        quote(&name)
+    );
+
+    // minecraft counts the length in utf-16 units
+    let len = command.encode_utf16().count();
+    if len > MAX_COMMAND_LENGTH {
+        return Err(GiveError::TooLong(len));
+    }
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tooltip::Color;
+
+    fn item() -> Item {
+        serde_json::from_str(
+            r#"{"displayName":"Test Wand","tier":"LEGENDARY","itemInfo":{"type":"WAND"},"requirements":{},"statuses":{}}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn give_leaves_out_page_only_text() {
+        let lines = [
+            Line::new()
+                .span("Test Wand", Color::Aqua)
+                .span(" [50.0%]", Color::Yellow)
+                .role(Role::Percent),
+            Line::new(),
+            Line::new().span("Average DPS: 100", Color::Gray).computed(),
+            Line::new(),
+            Line::new()
+                .span("+20% Spell Damage", Color::Green)
+                .span(" [75%]", Color::Yellow)
+                .role(Role::Percent),
+        ];
+
+        let Ok(command) = give_command(&item(), &lines) else {
+            panic!("the command is short");
+        };
+        assert!(command.starts_with("/give @p minecraft:stick[custom_name="));
+        assert!(command.contains("+20% Spell Damage"));
+        assert!(!command.contains("50.0%") && !command.contains("75%"));
+        assert!(!command.contains("Average DPS"));
+        // the separators of the dropped section are merged
+        assert_eq!(
+            render_legacy(&lines),
+            ["§bTest Wand", "", "§a+20% Spell Damage"]
+        );
+    }
+
+    #[test]
+    fn give_length_is_counted_in_utf16() {
+        // three bytes in utf-8 but one utf-16 unit each
+        let lore = [
+            Line::new(),
+            Line::new().span("✣".repeat(12000), Color::Gold),
+        ];
+        assert!(give_command(&item(), &lore).is_ok());
+
+        let lore = [
+            Line::new(),
+            Line::new().span("a".repeat(MAX_COMMAND_LENGTH), Color::Gold),
+        ];
+        assert!(matches!(
+            give_command(&item(), &lore),
+            Err(GiveError::TooLong(len)) if len > MAX_COMMAND_LENGTH
+        ));
+    }
+}
@@ -0,0 +1,777 @@
+//! Search for the best scoring build under a set of constraints
+//!
+//! Every slot is limited to its best few candidates and the combinations are searched depth first.
+//! Branches which cannot beat the builds found so far or cannot reach the thresholds are cut off.
+
+use sycamore::{futures::spawn_local_scoped, prelude::*};
+
+use crate::{
+    build::Slot,
+    read_ids,
+    recommend::{item_score, roll_value, RollMode},
+    registry::{base_name, IdRegistry},
+    skillpoints::SKILL_POINT_IDS,
+    solver::{lower_bound, solve_limited, within_limits, SolverItem, SKILL_NAMES},
+    weights::WeightProfile,
+    wynn::items::{Identification, Item, ItemList},
+};
+
+/// Most branches and skill point orders searched before giving up on finding anything better
+const MAX_NODES: usize = 500_000;
+/// Slots whose picks are searched one step at a time
+const PREFIX_SLOTS: usize = 2;
+
+/// A stat of a whole build which can be given a minimum
+#[derive(Clone, PartialEq, Eq)]
+pub enum Stat {
+    /// Health from the items and health ids
+    Health,
+    Id(Identification),
+}
+
+impl Stat {
+    /// Value of the stat on an item at a roll
+    fn value(&self, item: &Item, registry: &IdRegistry, mode: RollMode) -> f64 {
+        let ids = read_ids(item, registry);
+        let id_total = |key: &str| {
+            ids.iter()
+                .filter(|id| id.id.key() == key)
+                .map(|id| roll_value(id, mode, registry))
+                .sum::<f64>()
+        };
+
+        match self {
+            Stat::Health => {
+                item.defenses.as_ref().and_then(|d| d.health).unwrap_or(0) as f64
+                    + id_total("healthBonus")
+            }
+            Stat::Id(id) => id_total(id.key()),
+        }
+    }
+}
+
+/// Minimum value of a stat a build has to reach
+#[derive(Clone, PartialEq, Eq)]
+pub struct Threshold {
+    pub stat: Stat,
+    pub min: i32,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct OptimizerSettings {
+    pub max_level: i32,
+    pub mode: RollMode,
+    /// How many of the best items are tried in each slot
+    pub candidates: usize,
+    /// How many builds are reported
+    pub results: usize,
+    pub thresholds: Vec<Threshold>,
+}
+
+/// A build found by the optimizer
+#[derive(Clone, PartialEq)]
+pub struct OptimizedBuild {
+    pub items: Vec<(Slot, String)>,
+    pub score: f64,
+    /// Skill points which have to be assigned to wear the build
+    pub assigned: [i32; 5],
+}
+
+#[derive(Clone, PartialEq)]
+pub struct OptimizerResult {
+    /// Best builds with the best first
+    pub builds: Vec<OptimizedBuild>,
+    /// Whether the search ran out of branches before giving up
+    pub complete: bool,
+}
+
+/// An item which may be picked for a slot
+struct Candidate {
+    name: String,
+    score: f64,
+    /// Value of every threshold stat
+    stats: Vec<f64>,
+    skill_points: SolverItem,
+}
+
+/// Search for the best scoring builds meeting the level limit, skill point requirements and thresholds.
+///
+/// The search is split into steps so the page can be updated in between.
+pub struct Optimization {
+    search: Search,
+    /// Picks for the first slots which are searched one step at a time
+    prefixes: Vec<Vec<usize>>,
+}
+
+impl Optimization {
+    pub fn new(
+        item_list: &ItemList,
+        registry: &IdRegistry,
+        profile: &WeightProfile,
+        settings: &OptimizerSettings,
+    ) -> Result<Self, String> {
+        if settings.results == 0 {
+            return Err(String::from("At least one build has to be searched for"));
+        }
+
+        let mut slots = Vec::new();
+        for slot in Slot::ALL {
+            let candidates = candidates(item_list, registry, profile, settings, slot);
+            if !candidates.is_empty() {
+                slots.push((slot, candidates));
+            }
+        }
+        if slots.is_empty() {
+            return Err(format!(
+                "No items are allowed at level {}",
+                settings.max_level
+            ));
+        }
+
+        // best score and stats the slots from each index onwards can still add
+        let mut max_score = vec![0.0; slots.len() + 1];
+        let mut max_stats = vec![vec![0.0; settings.thresholds.len()]; slots.len() + 1];
+        for i in (0..slots.len()).rev() {
+            let candidates = &slots[i].1;
+            max_score[i] = max_score[i + 1]
+                + candidates
+                    .iter()
+                    .map(|c| c.score)
+                    .fold(f64::NEG_INFINITY, f64::max);
+            max_stats[i] = max_stats[i + 1]
+                .iter()
+                .enumerate()
+                .map(|(t, stat)| {
+                    stat + candidates
+                        .iter()
+                        .map(|c| c.stats[t])
+                        .fold(f64::NEG_INFINITY, f64::max)
+                })
+                .collect();
+        }
+
+        // every pick for the first two slots is one step
+        let mut prefixes = vec![Vec::new()];
+        for slot in 0..slots.len().min(PREFIX_SLOTS) {
+            prefixes = prefixes
+                .into_iter()
+                .flat_map(|prefix| {
+                    let first = first_candidate(&slots, slot, &prefix);
+                    (first..slots[slot].1.len()).map(move |i| {
+                        let mut prefix = prefix.clone();
+                        prefix.push(i);
+                        prefix
+                    })
+                })
+                .collect();
+        }
+        // steps are taken from the back
+        prefixes.reverse();
+
+        Ok(Self {
+            search: Search {
+                slots,
+                settings: settings.clone(),
+                max_score,
+                max_stats,
+                chosen: Vec::new(),
+                best: Vec::new(),
+                nodes: 0,
+                max_nodes: MAX_NODES,
+            },
+            prefixes,
+        })
+    }
+
+    /// Searches the builds starting with the next picks for the first slots.
+    ///
+    /// Returns false once there is nothing left to search.
+    pub fn step(&mut self) -> bool {
+        if self.search.nodes >= self.search.max_nodes {
+            return false;
+        }
+        let Some(prefix) = self.prefixes.pop() else {
+            return false;
+        };
+
+        self.search.search_prefix(&prefix);
+        true
+    }
+
+    /// Best builds found so far
+    pub fn result(&self) -> OptimizerResult {
+        OptimizerResult {
+            builds: self.search.best.clone(),
+            complete: self.search.nodes < self.search.max_nodes && self.prefixes.is_empty(),
+        }
+    }
+}
+
+/// Index of the first candidate worth trying in a slot after the picks for the slots before it
+fn first_candidate(slots: &[(Slot, Vec<Candidate>)], slot: usize, chosen: &[usize]) -> usize {
+    // the order of the two rings does not matter
+    match (slots[slot].0, slot.checked_sub(1).map(|s| slots[s].0)) {
+        (Slot::Ring2, Some(Slot::Ring1)) => chosen[slot - 1],
+        _ => 0,
+    }
+}
+
+/// The best scoring items for a slot and the best items for every threshold
+fn candidates(
+    item_list: &ItemList,
+    registry: &IdRegistry,
+    profile: &WeightProfile,
+    settings: &OptimizerSettings,
+    slot: Slot,
+) -> Vec<Candidate> {
+    let mut all = item_list
+        .items
+        .iter()
+        .filter(|item| {
+            slot.accepts(&item.item_info.r#type)
+                && item.requirements.level.unwrap_or(1) <= settings.max_level
+        })
+        .map(|item| {
+            let ids = read_ids(item, registry);
+            let mut skill_points = SolverItem::new(item, &ids);
+            for (bonus, key) in skill_points.bonuses.iter_mut().zip(SKILL_POINT_IDS) {
+                *bonus = ids
+                    .iter()
+                    .find(|id| id.id.key() == key)
+                    .map(|id| roll_value(id, settings.mode, registry) as i32)
+                    .unwrap_or(0);
+            }
+
+            Candidate {
+                name: item.name.clone(),
+                score: item_score(&ids, profile, settings.mode, registry),
+                stats: settings
+                    .thresholds
+                    .iter()
+                    .map(|t| t.stat.value(item, registry, settings.mode))
+                    .collect(),
+                skill_points,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut picked = Vec::new();
+
+    // items which help reaching the thresholds even if they score badly
+    for t in 0..settings.thresholds.len() {
+        all.sort_by(|a, b| b.stats[t].total_cmp(&a.stats[t]));
+        let count = usize::max(settings.candidates / 2, 1);
+        picked.extend(all.drain(..usize::min(count, all.len())));
+    }
+
+    all.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let count = settings.candidates.saturating_sub(picked.len()).max(1);
+    picked.extend(all.drain(..usize::min(count, all.len())));
+
+    // try the best scoring items first so the bound tightens quickly
+    picked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    picked
+}
+
+struct Search {
+    slots: Vec<(Slot, Vec<Candidate>)>,
+    settings: OptimizerSettings,
+    max_score: Vec<f64>,
+    max_stats: Vec<Vec<f64>>,
+    /// Candidate picked for every slot so far
+    chosen: Vec<usize>,
+    best: Vec<OptimizedBuild>,
+    /// Branches and skill point orders tried so far
+    nodes: usize,
+    /// Branches and skill point orders tried before giving up
+    max_nodes: usize,
+}
+
+impl Search {
+    /// Searches every build starting with the given picks
+    fn search_prefix(&mut self, prefix: &[usize]) {
+        let mut score = 0.0;
+        let mut stats = vec![0.0; self.settings.thresholds.len()];
+
+        self.chosen.clear();
+        for (slot, &i) in prefix.iter().enumerate() {
+            if !self.enter(slot, score, &stats) {
+                return;
+            }
+
+            let candidate = &self.slots[slot].1[i];
+            score += candidate.score;
+            for (stat, value) in stats.iter_mut().zip(&candidate.stats) {
+                *stat += value;
+            }
+            self.chosen.push(i);
+        }
+
+        self.search(prefix.len(), score, stats);
+    }
+
+    fn search(&mut self, slot: usize, score: f64, stats: Vec<f64>) {
+        if !self.enter(slot, score, &stats) {
+            return;
+        }
+
+        let first = first_candidate(&self.slots, slot, &self.chosen);
+        for i in first..self.slots[slot].1.len() {
+            let stats = stats
+                .iter()
+                .zip(&self.slots[slot].1[i].stats)
+                .map(|(a, b)| a + b)
+                .collect();
+            let score = score + self.slots[slot].1[i].score;
+
+            self.chosen.push(i);
+            self.search(slot + 1, score, stats);
+            self.chosen.pop();
+        }
+    }
+
+    /// Counts a branch and checks whether it is worth going deeper.
+    ///
+    /// Complete builds are added to the results here.
+    fn enter(&mut self, slot: usize, score: f64, stats: &[f64]) -> bool {
+        self.nodes += 1;
+        if self.nodes >= self.max_nodes {
+            return false;
+        }
+
+        // give up on branches which cannot make it into the results
+        if self.best.len() >= self.settings.results
+            && self
+                .best
+                .last()
+                .is_some_and(|worst| score + self.max_score[slot] <= worst.score)
+        {
+            return false;
+        }
+
+        // or cannot reach the thresholds
+        let reachable = self
+            .settings
+            .thresholds
+            .iter()
+            .enumerate()
+            .all(|(t, threshold)| stats[t] + self.max_stats[slot][t] >= threshold.min as f64);
+        if !reachable {
+            return false;
+        }
+
+        if slot == self.slots.len() {
+            self.finish(score);
+            return false;
+        }
+        true
+    }
+
+    /// Adds a complete build to the results if its skill point requirements can be met
+    fn finish(&mut self, score: f64) {
+        let items = self
+            .chosen
+            .iter()
+            .zip(&self.slots)
+            .map(|(i, (_, candidates))| candidates[*i].skill_points.clone())
+            .collect::<Vec<_>>();
+
+        // rule out most unwearable builds before trying equip orders
+        if !within_limits(&lower_bound(&items)) {
+            return;
+        }
+
+        // the solver counts towards the same budget
+        let (solution, nodes) = solve_limited(&items, self.max_nodes.saturating_sub(self.nodes));
+        self.nodes += nodes;
+        let Ok(solution) = solution else {
+            return;
+        };
+
+        let build = OptimizedBuild {
+            items: self
+                .chosen
+                .iter()
+                .zip(&self.slots)
+                .map(|(i, (slot, candidates))| (*slot, candidates[*i].name.clone()))
+                .collect(),
+            score,
+            assigned: solution.assigned,
+        };
+
+        let index = self.best.partition_point(|b| b.score >= score);
+        self.best.insert(index, build);
+        self.best.truncate(self.settings.results);
+    }
+}
+
+/// Lets the browser handle events and redraw the page before carrying on
+async fn yield_to_browser() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback(&resolve);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+#[derive(Prop)]
+pub struct OptimizerProps<'a> {
+    item_list: &'a ItemList,
+    registry: &'a IdRegistry,
+    profile: &'a ReadSignal<Option<WeightProfile>>,
+}
+
+#[component]
+pub fn Optimizer<'a, G: Html>(cx: Scope<'a>, props: OptimizerProps<'a>) -> View<G> {
+    let max_level = create_signal(cx, String::from("106"));
+    let mode = create_signal(cx, String::from("0"));
+    let candidates = create_signal(cx, String::from("6"));
+    let results = create_signal(cx, String::from("5"));
+
+    // thresholds as the stat key and the minimum
+    let thresholds = create_signal(cx, Vec::<(RcSignal<String>, RcSignal<String>)>::new());
+    let add_threshold = move |_| {
+        thresholds.modify().push((
+            create_rc_signal(String::from("health")),
+            create_rc_signal(String::new()),
+        ))
+    };
+
+    let stat_options = create_ref(
+        cx,
+        props
+            .registry
+            .sorted()
+            .into_iter()
+            .map(|id| (id.key().to_string(), base_name(id)))
+            .collect::<Vec<_>>(),
+    );
+
+    let result = create_signal(cx, None::<OptimizerResult>);
+    let running = create_signal(cx, false);
+    let error = create_signal(cx, None::<String>);
+
+    let run = move |_| {
+        if *running.get() {
+            return;
+        }
+        let Some(profile) = &*props.profile.get() else {
+            return;
+        };
+
+        // every minimum the user typed has to be used
+        let mut parsed = Vec::new();
+        for (stat, min) in thresholds.get().iter() {
+            let Ok(min) = min.get().trim().parse() else {
+                result.set(None);
+                error.set(Some(format!(
+                    "The minimum \"{}\" is not a whole number",
+                    min.get()
+                )));
+                return;
+            };
+            let stat = match stat.get().as_str() {
+                "health" => Stat::Health,
+                key => Stat::Id(Identification::new(key)),
+            };
+            parsed.push(Threshold { stat, min });
+        }
+
+        let settings = OptimizerSettings {
+            max_level: max_level.get().parse().unwrap_or(106),
+            mode: match mode.get().as_str() {
+                "1" => RollMode::Average,
+                "2" => RollMode::Perfect,
+                _ => RollMode::BestAchievable,
+            },
+            candidates: candidates.get().parse().unwrap_or(6),
+            results: results.get().parse().unwrap_or(5),
+            thresholds: parsed,
+        };
+
+        let mut optimization =
+            match Optimization::new(props.item_list, props.registry, profile, &settings) {
+                Ok(optimization) => optimization,
+                Err(e) => {
+                    result.set(None);
+                    error.set(Some(e));
+                    return;
+                }
+            };
+        error.set(None);
+        running.set(true);
+        spawn_local_scoped(cx, async move {
+            // show the builds found so far between steps
+            while optimization.step() {
+                result.set(Some(optimization.result()));
+                yield_to_browser().await;
+            }
+            result.set(Some(optimization.result()));
+            running.set(false);
+        });
+    };
+
+    view! {cx,
+        form(class="pure-form pure-form-stacked") {
+            div(class="pure-g") {
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Max level"}
+                    input(prop:type="number", bind:value=max_level)
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Rolls"}
+                    select(bind:value=mode) {
+                        option(value="0") {"Best achievable"}
+                        option(value="1") {"Average"}
+                        option(value="2") {"Perfect"}
+                    }
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Items tried per slot"}
+                    input(prop:type="number", bind:value=candidates)
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Builds"}
+                    input(prop:type="number", bind:value=results)
+                }
+            }
+        }
+        Indexed (
+            iterable=thresholds,
+            view=move |cx, (stat, min)| {
+                let stat = create_ref(cx, stat);
+                let min = create_ref(cx, min);
+                let stat_value = create_signal(cx, (*stat.get()).clone());
+                let min_value = create_signal(cx, (*min.get()).clone());
+                create_effect(cx, || stat.set((*stat_value.get()).clone()));
+                create_effect(cx, || min.set((*min_value.get()).clone()));
+
+                let options = View::new_fragment(
+                    stat_options
+                        .iter()
+                        .map(|(key, name)| {
+                            let key = key.clone();
+                            let name = name.clone();
+                            view! {cx, option(value=key) {(name)}}
+                        })
+                        .collect(),
+                );
+
+                view! {cx,
+                    form(class="pure-form", style="display: flex; gap: 5px; margin-bottom: 5px;") {
+                        select(style="flex-grow: 1;", bind:value=stat_value) {
+                            option(value="health") {"Health"}
+                            (options)
+                        }
+                        span {"≥"}
+                        input(style="width: 100px;", prop:type="number", bind:value=min_value)
+                    }
+                }
+            }
+        )
+        div(style="display: flex; gap: 5px;") {
+            button(class="pure-button", prop:type="button", on:click=add_threshold) {"Add minimum"}
+            button(class="pure-button", prop:type="button", on:click=move |_| { thresholds.modify().pop(); }) {"Remove minimum"}
+            button(class="pure-button pure-button-primary", prop:type="button", disabled=props.profile.get().is_none() || *running.get(), on:click=run) {
+                (if *running.get() { "Optimizing..." } else { "Optimize" })
+            }
+        }
+        (if props.profile.get().is_none() {
+            view! {cx, p {"Select a weight profile to optimize for"}}
+        } else {view!{cx,}})
+        (if let Some(e) = (*error.get()).clone() {
+            view! {cx, p(class="problem mc-red") {(e)}}
+        } else {view!{cx,}})
+        (match (*result.get()).clone() {
+            Some(result) if result.builds.is_empty() && !*running.get() => view! {cx, p(class="mc-red") {"No build meets the constraints"}},
+            Some(result) => {
+                let builds = View::new_fragment(
+                    result
+                        .builds
+                        .iter()
+                        .enumerate()
+                        .map(|(i, build)| {
+                            let items = build
+                                .items
+                                .iter()
+                                .map(|(slot, name)| format!("{}: {name}", slot.name()))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            let assigned = build
+                                .assigned
+                                .iter()
+                                .zip(SKILL_NAMES)
+                                .filter(|(p, _)| **p > 0)
+                                .map(|(p, name)| format!("{p} {name}"))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            let title = format!("#{} score {:.2}", i + 1, build.score);
+
+                            view! {cx,
+                                p {
+                                    b {(title)}
+                                    br {}
+                                    (items)
+                                    br {}
+                                    (if assigned.is_empty() { String::from("No skill points needed") } else { format!("Assign {assigned}") })
+                                }
+                            }
+                        })
+                        .collect(),
+                );
+
+                view! {cx,
+                    (builds)
+                    (if !result.complete && !*running.get() {
+                        view! {cx, p(class="problem mc-gold") {"The search was cut short, better builds may exist"}}
+                    } else {view!{cx,}})
+                }
+            }
+            None => view! {cx,},
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two helmets and two boots, the better spell damage items having less health
+    fn item_list() -> ItemList {
+        serde_json::from_str(
+            r#"{"items":[
+            {"displayName":"Sharp Cap","tier":"RARE","itemInfo":{"type":"HELMET"},"requirements":{"level":50},
+             "defenseTypes":{"health":100},"statuses":{"spellDamage":{"type":"PERCENTAGE","isFixed":true,"baseValue":20}}},
+            {"displayName":"Thick Cap","tier":"RARE","itemInfo":{"type":"HELMET"},"requirements":{"level":50},
+             "defenseTypes":{"health":500},"statuses":{"spellDamage":{"type":"PERCENTAGE","isFixed":true,"baseValue":10}}},
+            {"displayName":"Sharp Boots","tier":"RARE","itemInfo":{"type":"BOOTS"},"requirements":{"level":50},
+             "defenseTypes":{"health":100},"statuses":{"spellDamage":{"type":"PERCENTAGE","isFixed":true,"baseValue":15}}},
+            {"displayName":"Thick Boots","tier":"RARE","itemInfo":{"type":"BOOTS"},"requirements":{"level":50},
+             "defenseTypes":{"health":300},"statuses":{"spellDamage":{"type":"PERCENTAGE","isFixed":true,"baseValue":5}}},
+            {"displayName":"Godly Cap","tier":"MYTHIC","itemInfo":{"type":"HELMET"},"requirements":{"level":100},
+             "statuses":{"spellDamage":{"type":"PERCENTAGE","isFixed":true,"baseValue":90}}}
+            ],"identificationOrder":{"order":{"spellDamage":1,"healthBonus":2},"groups":[],"inverted":[]}}"#,
+        )
+        .unwrap()
+    }
+
+    fn profile() -> WeightProfile {
+        WeightProfile {
+            name: String::from("Spell"),
+            weights: [(Identification::new("spellDamage"), 1.0)]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn settings(thresholds: Vec<Threshold>) -> OptimizerSettings {
+        OptimizerSettings {
+            max_level: 80,
+            mode: RollMode::Perfect,
+            candidates: 4,
+            results: 1,
+            thresholds,
+        }
+    }
+
+    fn health(min: i32) -> Vec<Threshold> {
+        vec![Threshold {
+            stat: Stat::Health,
+            min,
+        }]
+    }
+
+    fn run(optimization: &mut Optimization) -> OptimizerResult {
+        while optimization.step() {}
+        optimization.result()
+    }
+
+    fn names(build: &OptimizedBuild) -> Vec<&str> {
+        build.items.iter().map(|(_, name)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn finds_the_best_build() {
+        let item_list = item_list();
+        let registry = IdRegistry::new(&item_list);
+        let mut optimization =
+            Optimization::new(&item_list, &registry, &profile(), &settings(Vec::new())).unwrap();
+
+        let result = run(&mut optimization);
+        assert!(result.complete);
+        assert_eq!(result.builds.len(), 1);
+        assert_eq!(names(&result.builds[0]), ["Sharp Cap", "Sharp Boots"]);
+    }
+
+    #[test]
+    fn thresholds_rule_out_better_builds() {
+        let item_list = item_list();
+        let registry = IdRegistry::new(&item_list);
+        let mut optimization =
+            Optimization::new(&item_list, &registry, &profile(), &settings(health(600))).unwrap();
+
+        let result = run(&mut optimization);
+        assert!(result.complete);
+        assert_eq!(names(&result.builds[0]), ["Thick Cap", "Sharp Boots"]);
+    }
+
+    #[test]
+    fn unreachable_thresholds_find_nothing() {
+        let item_list = item_list();
+        let registry = IdRegistry::new(&item_list);
+        let mut optimization =
+            Optimization::new(&item_list, &registry, &profile(), &settings(health(10000))).unwrap();
+
+        let result = run(&mut optimization);
+        assert!(result.complete);
+        assert!(result.builds.is_empty());
+    }
+
+    #[test]
+    fn gives_up_after_the_node_limit() {
+        let item_list = item_list();
+        let registry = IdRegistry::new(&item_list);
+        let mut optimization =
+            Optimization::new(&item_list, &registry, &profile(), &settings(Vec::new())).unwrap();
+        optimization.search.max_nodes = 2;
+
+        let result = run(&mut optimization);
+        assert!(!result.complete);
+        assert!(optimization.search.nodes >= 2);
+    }
+
+    #[test]
+    fn prefilter_keeps_threshold_items() {
+        let item_list = item_list();
+        let registry = IdRegistry::new(&item_list);
+        let mut settings = settings(health(0));
+        settings.candidates = 1;
+
+        // the best health helmet is kept besides the best scoring one
+        let helmets = candidates(&item_list, &registry, &profile(), &settings, Slot::Helmet);
+        let names = helmets.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["Sharp Cap", "Thick Cap"]);
+
+        // items over the level limit are never candidates
+        settings.max_level = 100;
+        settings.thresholds.clear();
+        let helmets = candidates(&item_list, &registry, &profile(), &settings, Slot::Helmet);
+        assert_eq!(helmets.len(), 1);
+        assert_eq!(helmets[0].name, "Godly Cap");
+    }
+
+    #[test]
+    fn rejects_settings_with_nothing_to_search() {
+        let item_list = item_list();
+        let registry = IdRegistry::new(&item_list);
+
+        let mut no_results = settings(Vec::new());
+        no_results.results = 0;
+        assert!(Optimization::new(&item_list, &registry, &profile(), &no_results).is_err());
+
+        let mut no_items = settings(Vec::new());
+        no_items.max_level = 10;
+        assert!(Optimization::new(&item_list, &registry, &profile(), &no_items).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! Rasterizing the item tooltip into a png image
+
+use crate::{
+    font::{glyph, pixel, ADVANCE, GLYPH_HEIGHT, GLYPH_WIDTH},
+    tooltip::Line,
+};
+
+/// Height of a line in pixels before scaling
+const LINE_HEIGHT: usize = GLYPH_HEIGHT + 2;
+const PADDING: usize = 4;
+/// Furthest italics lean a glyph to the right
+const ITALIC_SLANT: usize = (GLYPH_HEIGHT - 1) / 3;
+
+/// Colors of the minecraft tooltip background and border
+const BACKGROUND: [u8; 4] = [0x10, 0x00, 0x10, 0xf0];
+const BORDER_TOP: [u8; 3] = [0x50, 0x00, 0xff];
+const BORDER_BOTTOM: [u8; 3] = [0x28, 0x00, 0x7f];
+
+/// Smallest and largest scale of exported images
+pub const MIN_SCALE: usize = 1;
+pub const MAX_SCALE: usize = 4;
+
+/// An rgba image
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0; 4]; width * height],
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, color: [u8; 4]) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    /// Draws a glyph with its top left corner at the position
+    fn glyph(&mut self, x: usize, y: usize, c: char, color: [u8; 4], italic: bool) {
+        let glyph = glyph(c);
+        for gy in 0..GLYPH_HEIGHT {
+            // italics lean the top of the glyph to the right
+            let shear = if italic {
+                (GLYPH_HEIGHT - 1 - gy) / 3
+            } else {
+                0
+            };
+            for gx in 0..GLYPH_WIDTH {
+                if pixel(glyph, gx, gy) {
+                    self.set(x + gx + shear, y + gy, color);
+                }
+            }
+        }
+    }
+
+    /// Scales the image up by an integer factor keeping the pixels sharp
+    fn scaled(&self, scale: usize) -> Self {
+        let mut out = Self::new(self.width * scale, self.height * scale);
+        for y in 0..out.height {
+            for x in 0..out.width {
+                out.pixels[y * out.width + x] = self.pixels[(y / scale) * self.width + x / scale];
+            }
+        }
+        out
+    }
+}
+
+/// Renders the tooltip lines into a png image at a scale from 1 to 4
+pub fn render_png(lines: &[Line], scale: usize) -> Vec<u8> {
+    let chars = lines
+        .iter()
+        .map(|l| l.text().chars().count())
+        .max()
+        .unwrap_or(0);
+    // room for the shadow and the lean of italic text at the end of the longest line
+    let width = chars * ADVANCE + PADDING * 2 + 1 + ITALIC_SLANT;
+    let height = lines.len() * LINE_HEIGHT + PADDING * 2;
+
+    let mut canvas = Canvas::new(width, height);
+
+    // background with a border fading from top to bottom
+    for y in 0..height {
+        for x in 0..width {
+            canvas.set(x, y, BACKGROUND);
+        }
+
+        let t = y as f64 / (height - 1).max(1) as f64;
+        let mut border = [0xff; 4];
+        for i in 0..3 {
+            border[i] = (BORDER_TOP[i] as f64 * (1.0 - t) + BORDER_BOTTOM[i] as f64 * t) as u8;
+        }
+        if (1..height - 1).contains(&y) {
+            canvas.set(1, y, border);
+            canvas.set(width - 2, y, border);
+        }
+        if y == 1 || y == height - 2 {
+            for x in 1..width - 1 {
+                canvas.set(x, y, border);
+            }
+        }
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let y = PADDING + i * LINE_HEIGHT + 1;
+        let mut x = PADDING;
+
+        for span in &line.spans {
+            let [r, g, b] = span.color.rgb();
+            // minecraft draws text with a shadow at a quarter of the brightness
+            let shadow = [r / 4, g / 4, b / 4, 0xff];
+
+            for c in span.text.chars() {
+                canvas.glyph(x + 1, y + 1, c, shadow, span.italic);
+                canvas.glyph(x, y, c, [r, g, b, 0xff], span.italic);
+                x += ADVANCE;
+            }
+        }
+    }
+
+    encode_png(&canvas.scaled(scale.clamp(MIN_SCALE, MAX_SCALE)))
+}
+
+/// Encodes an image as a png using uncompressed deflate blocks
+fn encode_png(canvas: &Canvas) -> Vec<u8> {
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+    let mut header = Vec::new();
+    header.extend((canvas.width as u32).to_be_bytes());
+    header.extend((canvas.height as u32).to_be_bytes());
+    // 8 bit rgba without interlacing
+    header.extend([8, 6, 0, 0, 0]);
+    chunk(&mut png, b"IHDR", &header);
+
+    // every scanline starts with the filter type which is always none
+    let mut raw = Vec::with_capacity((canvas.width * 4 + 1) * canvas.height);
+    for row in canvas.pixels.chunks(canvas.width.max(1)) {
+        raw.push(0);
+        for pixel in row {
+            raw.extend(pixel);
+        }
+    }
+    chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut png, b"IEND", &[]);
+
+    png
+}
+
+/// Appends a png chunk with its length and checksum
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps data in a zlib stream made of stored deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // deflate with a 32k window and no preset dictionary
+    let mut out = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tooltip::Color;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(&[]), 1);
+    }
+
+    #[test]
+    fn single_pixel_png() {
+        let png = encode_png(&Canvas::new(1, 1));
+
+        let mut expected = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        // 1x1 rgba header
+        expected.extend([0, 0, 0, 13]);
+        expected.extend(b"IHDR");
+        expected.extend([0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]);
+        expected.extend([0x1f, 0x15, 0xc4, 0x89]);
+        // one stored block with the filter byte and a transparent pixel
+        expected.extend([0, 0, 0, 16]);
+        expected.extend(b"IDAT");
+        expected.extend([0x78, 0x01, 1, 5, 0, 0xfa, 0xff, 0, 0, 0, 0, 0]);
+        expected.extend([0x00, 0x05, 0x00, 0x01]);
+        expected.extend([0x64, 0x78, 0x95, 0x38]);
+        expected.extend([0, 0, 0, 0]);
+        expected.extend(b"IEND");
+        expected.extend([0xae, 0x42, 0x60, 0x82]);
+
+        assert_eq!(png, expected);
+    }
+
+    #[test]
+    fn italic_text_fits_the_image() {
+        let lines = [Line::new().span("MMMM", Color::White).italic()];
+        let png = render_png(&lines, 1);
+        let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]) as usize;
+
+        // the shadow of the top of the last glyph leans furthest to the right
+        let last = PADDING + 3 * ADVANCE + GLYPH_WIDTH + ITALIC_SLANT;
+        assert!(last < width - PADDING);
+    }
+}
@@ -0,0 +1,403 @@
+//! Effects of powders applied to items
+
+use crate::wynn::items::{DamageRange, DefenseTypes, Powder, Powders, Type};
+
+/// Stats of a single powder tier
+pub struct PowderStats {
+    /// Minimum elemental damage added to weapons
+    pub min: i32,
+    /// Maximum elemental damage added to weapons
+    pub max: i32,
+    /// % of the neutral damage of weapons converted to the element
+    pub conversion: i32,
+    /// Defence of the element added to armour
+    pub def_bonus: i32,
+    /// Defence of the weak element removed from armour
+    pub def_penalty: i32,
+}
+
+const fn stats(
+    min: i32,
+    max: i32,
+    conversion: i32,
+    def_bonus: i32,
+    def_penalty: i32,
+) -> PowderStats {
+    PowderStats {
+        min,
+        max,
+        conversion,
+        def_bonus,
+        def_penalty,
+    }
+}
+
+/// Stats of every powder indexed by element and tier
+const POWDER_STATS: [[PowderStats; 6]; 5] = [
+    // earth
+    [
+        stats(3, 6, 17, 2, 1),
+        stats(5, 8, 21, 4, 2),
+        stats(6, 10, 25, 8, 3),
+        stats(7, 10, 31, 14, 5),
+        stats(9, 11, 38, 22, 9),
+        stats(11, 13, 46, 30, 13),
+    ],
+    // thunder
+    [
+        stats(1, 8, 9, 3, 1),
+        stats(1, 12, 11, 5, 1),
+        stats(2, 15, 13, 9, 2),
+        stats(3, 15, 17, 14, 4),
+        stats(4, 17, 22, 20, 7),
+        stats(5, 20, 28, 28, 10),
+    ],
+    // water
+    [
+        stats(3, 4, 13, 3, 1),
+        stats(4, 6, 15, 6, 1),
+        stats(5, 8, 17, 11, 2),
+        stats(6, 8, 21, 18, 4),
+        stats(7, 10, 26, 28, 7),
+        stats(9, 11, 32, 40, 10),
+    ],
+    // fire
+    [
+        stats(2, 5, 14, 3, 1),
+        stats(4, 8, 16, 5, 2),
+        stats(6, 10, 19, 9, 3),
+        stats(6, 10, 24, 16, 5),
+        stats(8, 12, 30, 25, 9),
+        stats(10, 14, 37, 36, 13),
+    ],
+    // air
+    [
+        stats(2, 6, 11, 3, 1),
+        stats(3, 10, 14, 6, 2),
+        stats(4, 11, 17, 10, 3),
+        stats(5, 11, 22, 16, 5),
+        stats(7, 12, 28, 24, 9),
+        stats(8, 14, 35, 34, 13),
+    ],
+];
+
+impl Powder {
+    /// Stats of the powder from the powder table
+    pub fn stats(&self) -> &'static PowderStats {
+        &POWDER_STATS[self.element.index()][self.tier.clamp(1, 6) as usize - 1]
+    }
+}
+
+/// A value of an item which may have been changed by powders
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Powdered<T> {
+    pub value: T,
+    /// Whether any powder changed the value
+    pub powdered: bool,
+}
+
+/// Damages of a weapon after applying powders.
+///
+/// The damages are in the neutral, earth, thunder, water, fire, air order.
+pub fn apply_weapon_powders(
+    damages: &[Option<DamageRange>; 6],
+    powders: &[Powder],
+) -> [Option<Powdered<DamageRange>>; 6] {
+    let mut result = damages.map(|d| d.map(|d| (d.min as f64, d.max as f64)));
+    let mut powdered = [false; 6];
+
+    let neutral = result[0].unwrap_or((0.0, 0.0));
+    let mut remaining = neutral;
+
+    for powder in powders {
+        let stats = powder.stats();
+        let i = powder.element.index() + 1;
+        let (mut min, mut max) = result[i].unwrap_or((0.0, 0.0));
+
+        // convert a part of the neutral damage to the element
+        if remaining.1 > 0.0 {
+            let conversion = stats.conversion as f64 / 100.0;
+            let min_diff = f64::min(remaining.0, conversion * neutral.0);
+            let max_diff = f64::min(remaining.1, conversion * neutral.1);
+
+            remaining.0 -= min_diff;
+            remaining.1 -= max_diff;
+            min += min_diff;
+            max += max_diff;
+            powdered[0] = true;
+        }
+
+        min += stats.min as f64;
+        max += stats.max as f64;
+
+        result[i] = Some((min, max));
+        powdered[i] = true;
+    }
+
+    if result[0].is_some() {
+        result[0] = Some(remaining);
+    }
+
+    let mut out = [None; 6];
+    for (i, damage) in result.iter().enumerate() {
+        out[i] = damage.map(|(min, max)| Powdered {
+            value: DamageRange {
+                min: f64::round(min) as i32,
+                max: f64::round(max) as i32,
+            },
+            powdered: powdered[i],
+        });
+    }
+
+    out
+}
+
+/// Elemental defences of armour after applying powders.
+///
+/// The defences are in the earth, thunder, water, fire, air order.
+pub fn apply_armour_powders(
+    defenses: &DefenseTypes,
+    powders: &[Powder],
+) -> [Option<Powdered<i32>>; 5] {
+    let mut result = [
+        defenses.earth,
+        defenses.thunder,
+        defenses.water,
+        defenses.fire,
+        defenses.air,
+    ]
+    .map(|d| {
+        d.map(|value| Powdered {
+            value,
+            powdered: false,
+        })
+    });
+
+    for powder in powders {
+        let stats = powder.stats();
+        let i = powder.element.index();
+        // every element is weak against the one before it
+        let weak = (i + 4) % 5;
+
+        for (i, change) in [(i, stats.def_bonus), (weak, -stats.def_penalty)] {
+            let value = result[i].map(|d| d.value).unwrap_or(0) + change;
+            result[i] = Some(Powdered {
+                value,
+                powdered: true,
+            });
+        }
+    }
+
+    result
+}
+
+/// A single effect of a powder special
+#[derive(PartialEq)]
+pub struct SpecialEffect {
+    pub name: &'static str,
+    /// Value of the effect for every tier of the special
+    pub values: [f64; 5],
+    pub unit: &'static str,
+}
+
+/// A powder special ability
+#[derive(PartialEq)]
+pub struct PowderSpecial {
+    pub name: &'static str,
+    pub effects: &'static [SpecialEffect],
+}
+
+const fn effect(name: &'static str, values: [f64; 5], unit: &'static str) -> SpecialEffect {
+    SpecialEffect { name, values, unit }
+}
+
+/// Specials of weapons in the earth, thunder, water, fire, air order
+const WEAPON_SPECIALS: [PowderSpecial; 5] = [
+    PowderSpecial {
+        name: "Quake",
+        effects: &[
+            effect("Radius", [4.5, 5.0, 5.5, 6.0, 6.5], " blocks"),
+            effect("Damage", [155.0, 220.0, 285.0, 350.0, 415.0], "%"),
+        ],
+    },
+    PowderSpecial {
+        name: "Chain Lightning",
+        effects: &[
+            effect("Chains", [5.0, 6.0, 7.0, 8.0, 9.0], ""),
+            effect("Damage", [200.0, 225.0, 250.0, 275.0, 300.0], "%"),
+        ],
+    },
+    PowderSpecial {
+        name: "Curse",
+        effects: &[
+            effect("Duration", [7.0, 7.5, 8.0, 8.5, 9.0], "s"),
+            effect("Damage Boost", [90.0, 120.0, 150.0, 180.0, 210.0], "%"),
+        ],
+    },
+    PowderSpecial {
+        name: "Courage",
+        effects: &[
+            effect("Duration", [6.0, 6.5, 7.0, 7.5, 8.0], "s"),
+            effect("Damage", [75.0, 87.5, 100.0, 112.5, 125.0], "%"),
+            effect("Damage Boost", [70.0, 90.0, 110.0, 130.0, 150.0], "%"),
+        ],
+    },
+    PowderSpecial {
+        name: "Wind Prison",
+        effects: &[
+            effect("Duration", [3.0, 3.5, 4.0, 4.5, 5.0], "s"),
+            effect("Damage Boost", [400.0, 450.0, 500.0, 550.0, 600.0], "%"),
+            effect("Knockback", [8.0, 12.0, 16.0, 20.0, 24.0], " blocks"),
+        ],
+    },
+];
+
+/// Specials of armour in the earth, thunder, water, fire, air order
+const ARMOUR_SPECIALS: [PowderSpecial; 5] = [
+    PowderSpecial {
+        name: "Rage",
+        effects: &[effect(
+            "Damage",
+            [0.3, 0.4, 0.5, 0.6, 0.7],
+            "% per % ❤ missing",
+        )],
+    },
+    PowderSpecial {
+        name: "Kill Streak",
+        effects: &[
+            effect("Damage", [1.5, 3.0, 4.5, 6.0, 7.5], "% per mob killed"),
+            effect("Duration", [5.0, 5.0, 5.0, 5.0, 5.0], "s"),
+        ],
+    },
+    PowderSpecial {
+        name: "Concentration",
+        effects: &[
+            effect("Damage", [1.0, 2.0, 3.0, 4.0, 5.0], "% per mana used"),
+            effect("Duration", [1.0, 1.0, 1.0, 1.0, 1.0], "s"),
+        ],
+    },
+    PowderSpecial {
+        name: "Endurance",
+        effects: &[
+            effect("Damage", [2.0, 3.0, 4.0, 5.0, 6.0], "% per hit taken"),
+            effect("Duration", [8.0, 8.0, 8.0, 8.0, 8.0], "s"),
+        ],
+    },
+    PowderSpecial {
+        name: "Dodge",
+        effects: &[
+            effect("Damage", [2.0, 3.0, 4.0, 5.0, 6.0], "% near mobs"),
+            effect("Duration", [2.0, 3.0, 4.0, 5.0, 6.0], "s"),
+        ],
+    },
+];
+
+/// Lowest powder tier which counts towards a special
+const SPECIAL_MIN_TIER: u8 = 4;
+
+/// A powder special unlocked by the powders on an item
+#[derive(Clone, Copy, PartialEq)]
+pub struct ActiveSpecial {
+    pub special: &'static PowderSpecial,
+    pub element: Powders,
+    /// Tier of the special from 1 to 5
+    pub tier: usize,
+}
+
+impl Powders {
+    /// The special of the element for weapons or armour
+    pub fn special(self, weapon: bool) -> &'static PowderSpecial {
+        if weapon {
+            &WEAPON_SPECIALS[self.index()]
+        } else {
+            &ARMOUR_SPECIALS[self.index()]
+        }
+    }
+}
+
+/// Finds the powder special the powders on an item unlock.
+///
+/// Powders of tier IV, V and VI raise the special of their element by 1, 2 and 3 levels. If multiple
+/// elements qualify the one with the most levels wins with ties going to the element applied first.
+pub fn powder_special(r#type: &Type, powders: &[Powder]) -> Option<ActiveSpecial> {
+    if !r#type.is_weapon() && !r#type.is_armour() {
+        return None;
+    }
+
+    let mut counts = [0; 5];
+    let mut best: Option<Powders> = None;
+
+    for powder in powders.iter().filter(|p| p.tier >= SPECIAL_MIN_TIER) {
+        counts[powder.element.index()] += (powder.tier - SPECIAL_MIN_TIER + 1) as usize;
+
+        if best.is_none_or(|b| counts[powder.element.index()] > counts[b.index()]) {
+            best = Some(powder.element);
+        }
+    }
+
+    best.map(|element| ActiveSpecial {
+        special: element.special(r#type.is_weapon()),
+        element,
+        tier: usize::min(counts[element.index()], 5),
+    })
+}
+
+/// Roman numeral for powder and special tiers
+pub fn roman(n: usize) -> &'static str {
+    match n {
+        1 => "I",
+        2 => "II",
+        3 => "III",
+        4 => "IV",
+        5 => "V",
+        6 => "VI",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn powder(element: Powders, tier: u8) -> Powder {
+        Powder { element, tier }
+    }
+
+    #[test]
+    fn special_levels_by_tier() {
+        let special = |powders: &[Powder]| powder_special(&Type::WAND, powders).map(|s| s.tier);
+
+        assert_eq!(special(&[powder(Powders::FIRE, 3)]), None);
+        assert_eq!(special(&[powder(Powders::FIRE, 4)]), Some(1));
+        assert_eq!(special(&[powder(Powders::FIRE, 6)]), Some(3));
+        assert_eq!(
+            special(&[powder(Powders::FIRE, 4), powder(Powders::FIRE, 5)]),
+            Some(3)
+        );
+        // the level is capped at 5
+        assert_eq!(
+            special(&[powder(Powders::FIRE, 6), powder(Powders::FIRE, 6)]),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn special_element_with_most_levels() {
+        let special = powder_special(
+            &Type::WAND,
+            &[
+                powder(Powders::FIRE, 4),
+                powder(Powders::FIRE, 4),
+                powder(Powders::WATER, 6),
+            ],
+        );
+        assert!(special.is_some_and(|s| s.element == Powders::WATER && s.tier == 3));
+
+        // ties go to the element applied first
+        let special = powder_special(
+            &Type::WAND,
+            &[powder(Powders::AIR, 5), powder(Powders::EARTH, 5)],
+        );
+        assert!(special.is_some_and(|s| s.element == Powders::AIR));
+    }
+}
@@ -0,0 +1,58 @@
+use sycamore::prelude::*;
+
+use crate::wynn::items::{Powder, Powders};
+
+#[derive(Prop)]
+pub struct PowderSelectProps {
+    powder: RcSignal<Option<Powder>>,
+}
+
+/// Element and tier selectors for a single powder slot
+#[component]
+pub fn PowderSelect<G: Html>(cx: Scope, props: PowderSelectProps) -> View<G> {
+    let powder = create_ref(cx, props.powder);
+    let (element, tier) = if let Some(pow) = *powder.get() {
+        (pow.element.index() as i32, pow.tier)
+    } else {
+        (6, 6)
+    };
+    let element = create_signal(cx, element.to_string());
+    let tier = create_signal(cx, tier.to_string());
+
+    let changeval = move |_| {
+        powder.set(
+            Powders::from_i32(element.get().parse().unwrap_or_default()).map(|element| Powder {
+                element,
+                tier: tier.get().parse().unwrap_or(6),
+            }),
+        )
+    };
+
+    view!(cx,
+        form(class="pure-form", style="flex-grow: 1; display: flex; gap: 5px;") {
+            select(
+                style="flex-grow: 1;",
+                on:change=changeval,
+                bind:value=element
+            ) {
+                option(value="6") {"None"}
+                option(class="mc-dark-green", value="0") {"Earth"}
+                option(class="mc-gold", value="1") {"Thunder"}
+                option(class="mc-blue", value="2") {"Water"}
+                option(class="mc-red", value="3") {"Fire"}
+                option(value="4") {"Air"}
+            }
+            select(
+                on:change=changeval,
+                bind:value=tier
+            ) {
+                option(value="1") {"I"}
+                option(value="2") {"II"}
+                option(value="3") {"III"}
+                option(value="4") {"IV"}
+                option(value="5") {"V"}
+                option(value="6") {"VI"}
+            }
+        }
+    )
+}
@@ -0,0 +1,309 @@
+//! Ranking of every item by how well it fits a weight profile
+
+use sycamore::prelude::*;
+
+use crate::{
+    encoding::represented_value,
+    read_ids,
+    registry::IdRegistry,
+    weights::{weighted_total, WeightProfile},
+    wynn::items::{Id, Item, ItemList, Powders, Rarity, Type},
+};
+
+/// Which roll of the ids an item is scored with
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RollMode {
+    /// Best value an id string can carry
+    BestAchievable,
+    /// Middle of the roll range
+    Average,
+    /// Every id at its best roll
+    Perfect,
+}
+
+/// Value of an id at a roll
+pub fn roll_value(id: &Id, mode: RollMode, registry: &IdRegistry) -> f64 {
+    let best = if registry.is_inverted(&id.id) {
+        id.min_id()
+    } else {
+        id.max_id()
+    };
+
+    match mode {
+        RollMode::BestAchievable => represented_value(id, best) as f64,
+        RollMode::Average => (id.min_id() + id.max_id()) as f64 / 2.0,
+        RollMode::Perfect => best as f64,
+    }
+}
+
+/// Weighted score of an item at a roll
+pub fn item_score(
+    ids: &[Id],
+    profile: &WeightProfile,
+    mode: RollMode,
+    registry: &IdRegistry,
+) -> f64 {
+    weighted_total(
+        ids.iter()
+            .map(|id| (&id.id, roll_value(id, mode, registry))),
+        profile,
+        registry,
+    )
+}
+
+/// Limits on which items are recommended
+#[derive(Clone, PartialEq, Eq)]
+pub struct Constraints {
+    pub max_level: i32,
+    pub r#type: Option<Type>,
+    pub rarity: Option<Rarity>,
+    /// Highest requirement allowed in any skill
+    pub max_skill_points: Option<i32>,
+    /// Element the item has to use
+    pub element: Option<Powders>,
+}
+
+impl Constraints {
+    pub fn allows(&self, item: &Item) -> bool {
+        item.requirements.level.unwrap_or(1) <= self.max_level
+            && self
+                .r#type
+                .as_ref()
+                .is_none_or(|t| *t == item.item_info.r#type)
+            && self.rarity.as_ref().is_none_or(|r| *r == item.tier)
+            && self.max_skill_points.is_none_or(|max| {
+                item.requirements
+                    .skill_points()
+                    .iter()
+                    .all(|req| *req <= max)
+            })
+            && self.element.is_none_or(|e| has_element(item, e))
+    }
+}
+
+/// Whether an item deals damage, has defence or requires skill points of an element
+fn has_element(item: &Item, element: Powders) -> bool {
+    let damage = item
+        .damages
+        .as_ref()
+        .is_some_and(|d| d.ranges()[element.index() + 1].is_some());
+    let defence = item.defenses.as_ref().is_some_and(|d| {
+        match element {
+            Powders::EARTH => d.earth,
+            Powders::THUNDER => d.thunder,
+            Powders::WATER => d.water,
+            Powders::FIRE => d.fire,
+            Powders::AIR => d.air,
+        }
+        .is_some_and(|d| d > 0)
+    });
+    let requirement = item.requirements.skill_points()[element.index()] > 0;
+
+    damage || defence || requirement
+}
+
+/// An item and its score
+#[derive(Clone, PartialEq)]
+pub struct Recommendation {
+    pub name: String,
+    pub r#type: Type,
+    pub rarity: Rarity,
+    pub level: i32,
+    pub score: f64,
+}
+
+/// The highest scoring items allowed by the constraints
+pub fn recommend(
+    item_list: &ItemList,
+    registry: &IdRegistry,
+    profile: &WeightProfile,
+    constraints: &Constraints,
+    mode: RollMode,
+    count: usize,
+) -> Vec<Recommendation> {
+    let mut results = item_list
+        .items
+        .iter()
+        .filter(|item| constraints.allows(item))
+        .map(|item| Recommendation {
+            name: item.name.clone(),
+            r#type: item.item_info.r#type.clone(),
+            rarity: item.tier.clone(),
+            level: item.requirements.level.unwrap_or(1),
+            score: item_score(&read_ids(item, registry), profile, mode, registry),
+        })
+        .collect::<Vec<_>>();
+
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(count);
+    results
+}
+
+#[derive(Prop)]
+pub struct RecommendationsProps<'a> {
+    item_list: &'a ItemList,
+    registry: &'a IdRegistry,
+    profile: &'a ReadSignal<Option<WeightProfile>>,
+    /// Search box of the editor which results link into
+    searchtext: &'a Signal<String>,
+}
+
+#[component]
+pub fn Recommendations<'a, G: Html>(cx: Scope<'a>, props: RecommendationsProps<'a>) -> View<G> {
+    let max_level = create_signal(cx, String::from("106"));
+    let r#type = create_signal(cx, String::new());
+    let rarity = create_signal(cx, String::new());
+    let max_skill_points = create_signal(cx, String::new());
+    let element = create_signal(cx, String::new());
+    let mode = create_signal(cx, String::from("0"));
+    let count = create_signal(cx, String::from("20"));
+
+    let results = create_signal(cx, Vec::<Recommendation>::new());
+
+    let search = move |_| {
+        let Some(profile) = &*props.profile.get() else {
+            results.set(Vec::new());
+            return;
+        };
+
+        let constraints = Constraints {
+            max_level: max_level.get().parse().unwrap_or(106),
+            r#type: r#type
+                .get()
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| Type::ALL.get(i).cloned()),
+            rarity: rarity
+                .get()
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| Rarity::ALL.get(i).cloned()),
+            max_skill_points: max_skill_points.get().parse().ok(),
+            element: element.get().parse().ok().and_then(Powders::from_i32),
+        };
+        let mode = match mode.get().as_str() {
+            "1" => RollMode::Average,
+            "2" => RollMode::Perfect,
+            _ => RollMode::BestAchievable,
+        };
+
+        results.set(recommend(
+            props.item_list,
+            props.registry,
+            profile,
+            &constraints,
+            mode,
+            count.get().parse().unwrap_or(20),
+        ));
+    };
+
+    let types = View::new_fragment(
+        Type::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let name = t.to_string();
+                view! {cx, option(value=i.to_string()) {(name)}}
+            })
+            .collect(),
+    );
+    let rarities = View::new_fragment(
+        Rarity::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let name = r.to_string();
+                view! {cx, option(value=i.to_string()) {(name)}}
+            })
+            .collect(),
+    );
+
+    view! {cx,
+        form(class="pure-form pure-form-stacked") {
+            div(class="pure-g") {
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Max level"}
+                    input(prop:type="number", bind:value=max_level)
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Type"}
+                    select(bind:value=r#type) {
+                        option(value="") {"Any"}
+                        (types)
+                    }
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Rarity"}
+                    select(bind:value=rarity) {
+                        option(value="") {"Any"}
+                        (rarities)
+                    }
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Max skill point requirement"}
+                    input(prop:type="number", placeholder="Any", bind:value=max_skill_points)
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Element"}
+                    select(bind:value=element) {
+                        option(value="") {"Any"}
+                        option(class="mc-dark-green", value="0") {"Earth"}
+                        option(class="mc-gold", value="1") {"Thunder"}
+                        option(class="mc-blue", value="2") {"Water"}
+                        option(class="mc-red", value="3") {"Fire"}
+                        option(value="4") {"Air"}
+                    }
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Rolls"}
+                    select(bind:value=mode) {
+                        option(value="0") {"Best achievable"}
+                        option(value="1") {"Average"}
+                        option(value="2") {"Perfect"}
+                    }
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"Results"}
+                    input(prop:type="number", bind:value=count)
+                }
+                div(class="pure-u-1 pure-u-md-1-4") {
+                    label {"\u{a0}"}
+                    button(class="pure-button pure-button-primary", prop:type="button", disabled=props.profile.get().is_none(), on:click=search) {"Search"}
+                }
+            }
+        }
+        (if props.profile.get().is_none() {
+            view! {cx, p {"Select a weight profile to search with"}}
+        } else {view!{cx,}})
+        table(class="pure-table", style="width: 100%;") {
+            thead {
+                tr {
+                    th {"#"}
+                    th {"Item"}
+                    th {"Type"}
+                    th {"Lv"}
+                    th {"Score"}
+                }
+            }
+            tbody {
+                Indexed (
+                    iterable=create_selector(cx, || results.get().iter().cloned().enumerate().collect::<Vec<_>>()),
+                    view=move |cx, (i, result)| {
+                        let name = result.name.clone();
+                        let open = move |_| props.searchtext.set(name.clone());
+
+                        view! {cx,
+                            tr {
+                                td {(i + 1)}
+                                td { a(href="#", on:click=open) {(result.name)} }
+                                td {(format!("{} {}", result.rarity, result.r#type))}
+                                td {(result.level)}
+                                td {(format!("{:.2}", result.score))}
+                            }
+                        }
+                    }
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Central registry of everything known about identifications
+//!
+//! Static knowledge such as in-game names lives in the table below while the sort order, groups and
+//! inverted ids come from the wynntils api. Ids missing from either fall back to sensible defaults.
+
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+pub use crate::wynn::items::Encoding;
+use crate::wynn::items::{Id, Identification, ItemList, StatusType, Type};
+
+/// Whether higher values of an id are better
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Polarity {
+    /// Higher values are better
+    Normal,
+    /// Lower values are better, like spell costs
+    Inverted,
+}
+
+/// Static information about an id
+struct StaticInfo {
+    key: &'static str,
+    name: &'static str,
+    polarity: Polarity,
+}
+
+const fn info(key: &'static str, name: &'static str) -> StaticInfo {
+    StaticInfo {
+        key,
+        name,
+        polarity: Polarity::Normal,
+    }
+}
+
+const fn inverted(key: &'static str, name: &'static str) -> StaticInfo {
+    StaticInfo {
+        key,
+        name,
+        polarity: Polarity::Inverted,
+    }
+}
+
+/// In-game names of the ids known to differ from their api key
+const STATIC_INFO: &[StaticInfo] = &[
+    info("rawStrength", "Strength"),
+    info("rawDexterity", "Dexterity"),
+    info("rawIntelligence", "Intelligence"),
+    info("rawDefence", "Defence"),
+    info("rawAgility", "Agility"),
+    info("attackSpeedBonus", "Attack Speed"),
+    info("damageBonus", "Melee Damage"),
+    info("damageBonusRaw", "Melee Damage"),
+    info("spellDamage", "Spell Damage"),
+    info("spellDamageRaw", "Spell Damage"),
+    info("rainbowSpellDamageRaw", "Rainbow Spell Damage"),
+    info("healthRegen", "Health Regen"),
+    info("healthRegenRaw", "Health Regen"),
+    info("healthBonus", "Health"),
+    info("poison", "Poison"),
+    info("lifeSteal", "Life Steal"),
+    info("manaRegen", "Mana Regen"),
+    info("manaSteal", "Mana Steal"),
+    inverted("spellCostPct1", "1st Spell Cost"),
+    inverted("spellCostRaw1", "1st Spell Cost"),
+    inverted("spellCostPct2", "2nd Spell Cost"),
+    inverted("spellCostRaw2", "2nd Spell Cost"),
+    inverted("spellCostPct3", "3rd Spell Cost"),
+    inverted("spellCostRaw3", "3rd Spell Cost"),
+    inverted("spellCostPct4", "4th Spell Cost"),
+    inverted("spellCostRaw4", "4th Spell Cost"),
+    info("speed", "Walk Speed"),
+    info("soulPoints", "Soul Point Regen"),
+    info("emeraldStealing", "Stealing"),
+    info("xpBonus", "XP Bonus"),
+    info("gatherXpBonus", "Gather XP Bonus"),
+    info("earthDamageBonus", "Earth Damage"),
+    info("thunderDamageBonus", "Thunder Damage"),
+    info("waterDamageBonus", "Water Damage"),
+    info("fireDamageBonus", "Fire Damage"),
+    info("airDamageBonus", "Air Damage"),
+    info("earthDefenseBonus", "Earth Defence"),
+    info("thunderDefenseBonus", "Thunder Defence"),
+    info("waterDefenseBonus", "Water Defence"),
+    info("fireDefenseBonus", "Fire Defence"),
+    info("airDefenseBonus", "Air Defence"),
+];
+
+fn static_info(key: &str) -> Option<&'static StaticInfo> {
+    STATIC_INFO.iter().find(|i| i.key == key)
+}
+
+/// Name of an id without any item specific context.
+///
+/// Unknown ids get their api key split into words.
+pub fn base_name(id: &Identification) -> String {
+    if let Some(info) = static_info(id.key()) {
+        return info.name.to_string();
+    }
+
+    let mut s = String::new();
+    let mut itr = id.key().chars();
+
+    if let Some(c) = itr.next() {
+        s.push(c.to_ascii_uppercase());
+    }
+
+    for c in itr {
+        if c.is_uppercase() {
+            s.push(' ');
+        }
+        s.push(c);
+    }
+
+    s
+}
+
+/// Major ids whose name is not just their api key in title case
+const MAJOR_ID_NAMES: &[(&str, &str)] = &[("ROVINGASSASSIN", "Roving Assassin")];
+
+/// Name of a major id as shown in game.
+///
+/// The api sometimes only gives keys like `PLAGUE` which get turned into words.
+pub fn major_id_name(name: &str) -> String {
+    if let Some((_, display)) = MAJOR_ID_NAMES.iter().find(|(key, _)| *key == name) {
+        return display.to_string();
+    }
+    // names which are already readable are kept
+    if name.chars().any(|c| c.is_lowercase()) {
+        return name.to_string();
+    }
+
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap_or_default();
+            format!("{first}{}", chars.as_str().to_lowercase())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Everything known about a single id on an item
+#[derive(Clone, PartialEq, Eq)]
+pub struct IdInfo {
+    /// Key of the id in the wynntils api
+    pub key: Identification,
+    /// Position of the id in the wynntils sort order
+    pub order: Option<i32>,
+    /// Name of the id as shown in game
+    pub name: String,
+    /// Unit shown after the value of the id
+    pub unit: &'static str,
+    pub polarity: Polarity,
+    /// Range of sort indexes of the group the id is displayed in
+    pub group: Option<RangeInclusive<i32>>,
+    pub encoding: Encoding,
+}
+
+/// Registry combining the static id table with the ordering and items from the wynntils api
+pub struct IdRegistry {
+    order: BTreeMap<Identification, i32>,
+    groups: Vec<RangeInclusive<i32>>,
+    inverted: Vec<Identification>,
+    /// Largest roll of each id on any item
+    scales: BTreeMap<Identification, f64>,
+}
+
+impl IdRegistry {
+    pub fn new(item_list: &ItemList) -> Self {
+        let order = &item_list.order;
+
+        let mut scales = BTreeMap::new();
+        for item in &item_list.items {
+            for (id, status) in &item.statuses {
+                // rollable ids go up to 130% of the base value
+                let multiplier = if status.fixed { 1.0 } else { 1.3 };
+                let largest = f64::abs(status.base as f64) * multiplier;
+
+                let scale = scales.entry(id.clone()).or_insert(0.0);
+                *scale = f64::max(*scale, largest);
+            }
+        }
+
+        Self {
+            order: order.order.clone(),
+            groups: order.groups.iter().map(|g| g.as_range()).collect(),
+            inverted: order.inverted.clone(),
+            scales,
+        }
+    }
+
+    /// Largest value the id rolls on any item, for putting ids with different ranges on one scale
+    pub fn scale(&self, id: &Identification) -> f64 {
+        self.scales
+            .get(id)
+            .copied()
+            .filter(|s| *s > 0.0)
+            .unwrap_or(1.0)
+    }
+
+    /// Everything known about an id on an item of the given type
+    pub fn get(&self, id: &Id, r#type: &Type) -> IdInfo {
+        IdInfo {
+            key: id.id.clone(),
+            order: self.sort_index(&id.id),
+            name: self.name(&id.id, r#type),
+            unit: self.unit(&id.id, id.idtype),
+            polarity: self.polarity(&id.id),
+            group: self.group(&id.id),
+            encoding: id.encoding(),
+        }
+    }
+
+    /// Position of the id in the wynntils sort order
+    pub fn sort_index(&self, id: &Identification) -> Option<i32> {
+        self.order.get(id).copied()
+    }
+
+    /// All ids in the wynntils sort order
+    pub fn sorted(&self) -> Vec<&Identification> {
+        let mut ids = self.order.iter().collect::<Vec<_>>();
+        ids.sort_by_key(|(_, i)| **i);
+        ids.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Range of sort indexes of the group the id is displayed in
+    pub fn group(&self, id: &Identification) -> Option<RangeInclusive<i32>> {
+        let index = self.sort_index(id)?;
+        self.groups.iter().find(|g| g.contains(&index)).cloned()
+    }
+
+    pub fn polarity(&self, id: &Identification) -> Polarity {
+        let inverted = self.inverted.contains(id)
+            || static_info(id.key()).map(|i| i.polarity) == Some(Polarity::Inverted);
+
+        if inverted {
+            Polarity::Inverted
+        } else {
+            Polarity::Normal
+        }
+    }
+
+    pub fn is_inverted(&self, id: &Identification) -> bool {
+        self.polarity(id) == Polarity::Inverted
+    }
+
+    /// Name of the id as shown in game on an item of the given type.
+    ///
+    /// Spell costs are named after the spells of the class using the item.
+    pub fn name(&self, id: &Identification, r#type: &Type) -> String {
+        match (spell_cost(id), r#type.class()) {
+            (Some((n, _)), Some(class)) => {
+                format!("{} Spell Cost", class.spell_names()[n - 1])
+            }
+            _ => base_name(id),
+        }
+    }
+
+    /// Unit shown after the value of the id
+    pub fn unit(&self, id: &Identification, idtype: StatusType) -> &'static str {
+        if let Some((_, percent)) = spell_cost(id) {
+            return if percent { "%" } else { "" };
+        }
+
+        match idtype {
+            StatusType::PERCENTAGE => "%",
+            StatusType::INTEGER => "",
+            StatusType::TIER => " tier",
+            StatusType::FOUR_SECONDS => "/4s",
+            StatusType::THREE_SECONDS => "/3s",
+        }
+    }
+}
+
+/// The spell number and whether the cost is a % if the id is a spell cost id
+fn spell_cost(id: &Identification) -> Option<(usize, bool)> {
+    let (percent, spell) = if let Some(spell) = id.key().strip_prefix("spellCostPct") {
+        (true, spell)
+    } else {
+        (false, id.key().strip_prefix("spellCostRaw")?)
+    };
+
+    match spell.parse() {
+        Ok(n @ 1..=4) => Some((n, percent)),
+        _ => None,
+    }
+}
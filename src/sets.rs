@@ -0,0 +1,38 @@
+//! Set membership and set bonuses
+
+use crate::wynn::items::{Item, ItemList, ItemSet};
+
+/// Loads every set in the item list.
+///
+/// The pieces of each set come from the item list. Bonuses come from the table bundled with the app,
+/// sets missing from it are loaded without bonuses.
+pub fn load_sets(item_list: &ItemList) -> Vec<ItemSet> {
+    let mut sets: Vec<ItemSet> = serde_json::from_str(include_str!("data/sets.json"))
+        .expect("the bundled set table is invalid");
+
+    for item in &item_list.items {
+        let Some(name) = &item.item_info.set else {
+            continue;
+        };
+
+        match sets.iter_mut().find(|set| set.name == *name) {
+            Some(set) if !set.items.contains(&item.name) => set.items.push(item.name.clone()),
+            Some(_) => {}
+            None => sets.push(ItemSet {
+                name: name.clone(),
+                items: vec![item.name.clone()],
+                bonuses: Vec::new(),
+            }),
+        }
+    }
+
+    sets
+}
+
+/// Finds the set an item belongs to
+pub fn find_set<'a>(item: &Item, sets: &'a [ItemSet]) -> Option<&'a ItemSet> {
+    // prefer the set name from the api and fall back to the item lists in the table
+    sets.iter()
+        .find(|set| item.item_info.set.as_ref() == Some(&set.name))
+        .or_else(|| sets.iter().find(|set| set.items.contains(&item.name)))
+}
@@ -0,0 +1,110 @@
+//! Effects of skill points on damage and defence
+
+use crate::{
+    damage::{id_value, AverageDamage},
+    wynn::items::{Id, Item},
+};
+
+/// Ids adding skill points in the strength, dexterity, intelligence, defence, agility order
+pub const SKILL_POINT_IDS: [&str; 5] = [
+    "rawStrength",
+    "rawDexterity",
+    "rawIntelligence",
+    "rawDefence",
+    "rawAgility",
+];
+
+/// Skill points past this do not give any more bonuses
+const MAX_EFFECTIVE_POINTS: i32 = 150;
+
+/// How much of the skill point % defence turns into damage reduction
+const DEFENCE_MULTIPLIER: f64 = 0.867;
+/// How much of the skill point % agility turns into dodge chance
+const AGILITY_MULTIPLIER: f64 = 0.951;
+
+/// The % bonus given by an amount of skill points following wynncraft's scaling curve
+pub fn skill_point_percent(points: i32) -> f64 {
+    if points <= 0 {
+        return 0.0;
+    }
+
+    let points = i32::min(points, MAX_EFFECTIVE_POINTS);
+    let r: f64 = 0.9908;
+
+    (r / (1.0 - r) * (1.0 - r.powi(points))) / 100.0
+}
+
+/// Total skill points of the assumed points and the skill point ids of the item
+pub fn total_skill_points(assumed: [i32; 5], ids: &[Id]) -> [i32; 5] {
+    let mut total = assumed;
+    for (points, id) in total.iter_mut().zip(SKILL_POINT_IDS) {
+        *points += id_value(ids, id);
+    }
+    total
+}
+
+/// Effects of a set of skill points on an item
+#[derive(Clone, Copy, PartialEq)]
+pub struct SkillPointEffects {
+    /// Skill points including the ones from the item
+    pub points: [i32; 5],
+    /// Damage bonus from strength
+    pub damage_bonus: f64,
+    /// Critical hit chance from dexterity
+    pub crit_chance: f64,
+    /// Damage reduction from defence
+    pub damage_reduction: f64,
+    /// Dodge chance from agility
+    pub dodge_chance: f64,
+    /// Average damage with strength and critical hits
+    pub damage: Option<AverageDamage>,
+    /// Health of a player wearing the item at its level
+    pub health: i32,
+    /// Health with defence and agility taken into account
+    pub effective_health: f64,
+}
+
+/// Base health of a player at a level
+fn base_health(level: i32) -> i32 {
+    5 * level + 5
+}
+
+/// Calculates the effects of skill points on the damage and health of an item
+pub fn skill_point_effects(
+    item: &Item,
+    ids: &[Id],
+    assumed: [i32; 5],
+    damage: Option<AverageDamage>,
+) -> SkillPointEffects {
+    let points = total_skill_points(assumed, ids);
+
+    let damage_bonus = skill_point_percent(points[0]);
+    let crit_chance = skill_point_percent(points[1]);
+    let damage_reduction = skill_point_percent(points[3]) * DEFENCE_MULTIPLIER;
+    let dodge_chance = skill_point_percent(points[4]) * AGILITY_MULTIPLIER;
+
+    // critical hits do double damage
+    let multiplier = (1.0 + damage_bonus) * (1.0 + crit_chance);
+    let damage = damage.map(|d| AverageDamage {
+        per_hit: d.per_hit * multiplier,
+        per_second: d.per_second * multiplier,
+        speed: d.speed,
+    });
+
+    let health = base_health(item.requirements.level.unwrap_or(1))
+        + item.defenses.as_ref().and_then(|d| d.health).unwrap_or(0)
+        + id_value(ids, "healthBonus");
+    let effective_health =
+        health as f64 / (0.1 * dodge_chance + (1.0 - dodge_chance) * (1.0 - damage_reduction));
+
+    SkillPointEffects {
+        points,
+        damage_bonus,
+        crit_chance,
+        damage_reduction,
+        dodge_chance,
+        damage,
+        health,
+        effective_health,
+    }
+}
@@ -14,6 +14,14 @@ pub fn Slider<G: Html>(cx: Scope, props: SliderProps) -> View<G> {
 
     let view = create_signal(cx, val.get().to_string());
 
+    // keep the inputs in sync when the value is changed from elsewhere
+    create_effect(cx, move || {
+        let value = *val.get();
+        if view.get_untracked().parse::<i32>().ok() != Some(value) {
+            view.set(value.to_string());
+        }
+    });
+
     let tryset = move |_| {
         if let Ok(value) = view.get().parse::<i32>() {
             val.set(value);
@@ -0,0 +1,421 @@
+//! Solver for the skill point requirements of a set of items
+//!
+//! Skill points given by the ids of an item only count once the item is worn, so the order items are
+//! equipped in decides how many points have to be assigned by hand. Every item still has to meet its
+//! requirements once everything is worn since negative skill point ids can take points away.
+
+use std::fmt::Display;
+
+use crate::{
+    damage::id_value,
+    skillpoints::SKILL_POINT_IDS,
+    wynn::items::{Id, Item},
+};
+
+/// Most skill points which can be assigned to a single skill
+pub const MAX_ASSIGNED: i32 = 100;
+/// Most skill points which can be assigned in total
+pub const MAX_ASSIGNED_TOTAL: i32 = 200;
+
+/// Names of the skills in the strength, dexterity, intelligence, defence, agility order
+pub const SKILL_NAMES: [&str; 5] = [
+    "Strength",
+    "Dexterity",
+    "Intelligence",
+    "Defence",
+    "Agility",
+];
+
+/// Skill point requirements and bonuses of a single item
+#[derive(Clone, PartialEq, Eq)]
+pub struct SolverItem {
+    pub name: String,
+    pub required: [i32; 5],
+    pub bonuses: [i32; 5],
+}
+
+impl SolverItem {
+    pub fn new(item: &Item, ids: &[Id]) -> Self {
+        Self {
+            name: item.name.clone(),
+            required: item.requirements.skill_points(),
+            bonuses: SKILL_POINT_IDS.map(|id| id_value(ids, id)),
+        }
+    }
+}
+
+/// Skill points to assign and the order to equip the items in
+#[derive(Clone, PartialEq, Eq)]
+pub struct Solution {
+    /// Skill points assigned by hand in the strength, dexterity, intelligence, defence, agility order
+    pub assigned: [i32; 5],
+    /// Indexes of the items in the order they should be equipped in
+    pub order: Vec<usize>,
+}
+
+impl Solution {
+    pub fn total(&self) -> i32 {
+        self.assigned.iter().sum()
+    }
+}
+
+/// Reason no equip order can meet the requirements
+#[derive(Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// A requirement needs more points in a skill than can be assigned to it
+    SkillCap {
+        skill: usize,
+        needed: i32,
+        /// Item whose requirement needs the points
+        item: String,
+    },
+    /// The requirements need more points than can be assigned in total
+    Total { needed: i32 },
+    /// The search ran out of orders to try before finding any
+    GaveUp,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::SkillCap {
+                skill,
+                needed,
+                item,
+            } => write!(
+                f,
+                "The {} requirement of {item} needs {needed} assigned points, over the limit of {MAX_ASSIGNED}",
+                SKILL_NAMES[*skill]
+            ),
+            SolveError::Total { needed } => write!(
+                f,
+                "The requirements need {needed} assigned points, over the limit of {MAX_ASSIGNED_TOTAL}"
+            ),
+            SolveError::GaveUp => write!(f, "Too many items give skill points to find an equip order"),
+        }
+    }
+}
+
+/// Finds the fewest skill points to assign and an equip order which meets every requirement.
+///
+/// Only items with skill point ids change the outcome of the order so only those are permuted, the
+/// rest are equipped after them.
+pub fn solve(items: &[SolverItem]) -> Result<Solution, SolveError> {
+    solve_limited(items, usize::MAX).0
+}
+
+/// Like [`solve`] but gives up after trying `limit` partial orders.
+///
+/// Also returns how many partial orders were tried. The best order found before giving up is kept
+/// even if it is not the best one.
+pub fn solve_limited(items: &[SolverItem], limit: usize) -> (Result<Solution, SolveError>, usize) {
+    let mut totals = [0; 5];
+    for item in items {
+        for (total, bonus) in totals.iter_mut().zip(item.bonuses) {
+            *total += bonus;
+        }
+    }
+
+    // every requirement has to hold once everything is worn
+    let mut base = Assigned::default();
+    for (i, item) in items.iter().enumerate() {
+        base.require(item, &totals, i);
+    }
+
+    let (bonus_items, rest): (Vec<usize>, Vec<usize>) =
+        (0..items.len()).partition(|i| items[*i].bonuses.iter().any(|b| *b != 0));
+
+    let mut search = Search {
+        items,
+        best: None,
+        order: Vec::new(),
+        used: vec![false; items.len()],
+        nodes: 0,
+        limit,
+    };
+    search.permute(&bonus_items, [0; 5], base);
+    let nodes = search.nodes;
+
+    let Some((assigned, mut order)) = search.best else {
+        return (Err(SolveError::GaveUp), nodes);
+    };
+    order.extend(rest);
+
+    (check(assigned, order, items), nodes)
+}
+
+/// Turns the points needed by an order into a solution if they are within the limits
+fn check(
+    assigned: Assigned,
+    order: Vec<usize>,
+    items: &[SolverItem],
+) -> Result<Solution, SolveError> {
+    if let Some(s) = (0..5).find(|s| assigned.points[*s] > MAX_ASSIGNED) {
+        return Err(SolveError::SkillCap {
+            skill: s,
+            needed: assigned.points[s],
+            item: assigned.binding[s]
+                .map(|i| items[i].name.clone())
+                .unwrap_or_default(),
+        });
+    }
+
+    let needed = assigned.points.iter().sum();
+    if needed > MAX_ASSIGNED_TOTAL {
+        return Err(SolveError::Total { needed });
+    }
+
+    Ok(Solution {
+        assigned: assigned.points,
+        order,
+    })
+}
+
+/// Fewest skill points each skill needs whatever order the items are equipped in.
+///
+/// Items never benefit from their own bonuses, so each requirement needs at least the required points
+/// minus the positive bonuses of every other item, and still has to hold once everything is worn.
+/// This is much cheaper than [`solve`] for ruling out builds which can never be worn.
+pub fn lower_bound(items: &[SolverItem]) -> [i32; 5] {
+    let mut positive = [0; 5];
+    let mut totals = [0; 5];
+    for item in items {
+        for s in 0..5 {
+            positive[s] += item.bonuses[s].max(0);
+            totals[s] += item.bonuses[s];
+        }
+    }
+
+    let mut bound = [0; 5];
+    for item in items {
+        for s in 0..5 {
+            if item.required[s] > 0 {
+                let before = item.required[s] - (positive[s] - item.bonuses[s].max(0));
+                let after = item.required[s] - totals[s];
+                bound[s] = bound[s].max(before).max(after);
+            }
+        }
+    }
+    bound
+}
+
+/// Whether the skill points needed by [`lower_bound`] are within the limits
+pub fn within_limits(bound: &[i32; 5]) -> bool {
+    bound.iter().all(|b| *b <= MAX_ASSIGNED) && bound.iter().sum::<i32>() <= MAX_ASSIGNED_TOTAL
+}
+
+/// Skill points needed so far and the items needing them
+#[derive(Clone, Copy, Default)]
+struct Assigned {
+    points: [i32; 5],
+    binding: [Option<usize>; 5],
+}
+
+impl Assigned {
+    fn raise(&mut self, skill: usize, needed: i32, item: usize) {
+        if needed > self.points[skill] {
+            self.points[skill] = needed;
+            self.binding[skill] = Some(item);
+        }
+    }
+
+    /// Raises the points to meet the requirements of an item on top of the skill points of worn items
+    fn require(&mut self, item: &SolverItem, worn: &[i32; 5], index: usize) {
+        for (s, (required, worn)) in item.required.iter().zip(worn).enumerate() {
+            if *required > 0 {
+                self.raise(s, required - worn, index);
+            }
+        }
+    }
+
+    fn total(&self) -> i32 {
+        self.points.iter().sum()
+    }
+
+    fn within_caps(&self) -> bool {
+        self.points.iter().all(|p| *p <= MAX_ASSIGNED)
+    }
+}
+
+struct Search<'a> {
+    items: &'a [SolverItem],
+    best: Option<(Assigned, Vec<usize>)>,
+    order: Vec<usize>,
+    used: Vec<bool>,
+    /// Partial orders tried so far
+    nodes: usize,
+    limit: usize,
+}
+
+impl Search<'_> {
+    /// Tries every order of the remaining items keeping the one needing the fewest points
+    fn permute(&mut self, candidates: &[usize], worn: [i32; 5], assigned: Assigned) {
+        if self.nodes >= self.limit {
+            return;
+        }
+        self.nodes += 1;
+
+        // assigned points never go down deeper in the search
+        if let Some((best, _)) = &self.best {
+            if Self::better(best, &assigned) {
+                return;
+            }
+        }
+
+        if self.order.len() == candidates.len() {
+            self.best = Some((assigned, self.order.clone()));
+            return;
+        }
+
+        for &i in candidates {
+            if self.used[i] {
+                continue;
+            }
+
+            let item = &self.items[i];
+            let mut next = assigned;
+            next.require(item, &worn, i);
+
+            let mut worn = worn;
+            for (w, bonus) in worn.iter_mut().zip(item.bonuses) {
+                *w += bonus;
+            }
+
+            self.used[i] = true;
+            self.order.push(i);
+            self.permute(candidates, worn, next);
+            self.order.pop();
+            self.used[i] = false;
+        }
+    }
+
+    /// Whether a is at least as good as b, preferring solutions within the per skill limit
+    fn better(a: &Assigned, b: &Assigned) -> bool {
+        match (a.within_caps(), b.within_caps()) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => a.total() <= b.total(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, required: [i32; 5], bonuses: [i32; 5]) -> SolverItem {
+        SolverItem {
+            name: name.to_string(),
+            required,
+            bonuses,
+        }
+    }
+
+    #[test]
+    fn bonus_items_first() {
+        let items = [
+            item("Heavy", [50, 0, 0, 0, 0], [0; 5]),
+            item("Booster", [20, 0, 0, 0, 0], [30, 0, 0, 0, 0]),
+        ];
+
+        // wearing the booster first leaves 20 points to assign instead of 50
+        let Ok(solution) = solve(&items) else {
+            panic!("the build should be solvable");
+        };
+        assert_eq!(solution.assigned, [20, 0, 0, 0, 0]);
+        assert_eq!(solution.order, vec![1, 0]);
+    }
+
+    #[test]
+    fn order_between_bonus_items() {
+        let items = [
+            item("Strong", [40, 0, 0, 0, 0], [0, 0, 0, 0, 0]),
+            item("Swift", [0, 0, 0, 0, 30], [40, 0, 0, 0, 0]),
+            item("Windy", [0, 0, 0, 0, 0], [0, 0, 0, 0, 30]),
+        ];
+
+        let Ok(solution) = solve(&items) else {
+            panic!("the build should be solvable");
+        };
+        assert_eq!(solution.total(), 0);
+        assert_eq!(solution.order, vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn skill_over_the_cap() {
+        let items = [
+            item("Light", [0, 10, 0, 0, 0], [0; 5]),
+            item("Brute", [120, 0, 0, 0, 0], [0; 5]),
+        ];
+
+        assert!(
+            solve(&items)
+                == Err(SolveError::SkillCap {
+                    skill: 0,
+                    needed: 120,
+                    item: String::from("Brute"),
+                })
+        );
+    }
+
+    #[test]
+    fn negative_bonus_counts() {
+        // the penalty of the second item applies once everything is worn
+        let items = [
+            item("Brute", [100, 0, 0, 0, 0], [0; 5]),
+            item("Cursed", [0; 5], [-10, 0, 0, 0, 0]),
+        ];
+
+        assert!(matches!(
+            solve(&items),
+            Err(SolveError::SkillCap { needed: 110, .. })
+        ));
+    }
+
+    #[test]
+    fn total_cap() {
+        let at_cap = [
+            item("Strong", [100, 0, 0, 0, 0], [0; 5]),
+            item("Smart", [0, 0, 100, 0, 0], [0; 5]),
+        ];
+        assert!(matches!(solve(&at_cap), Ok(s) if s.total() == MAX_ASSIGNED_TOTAL));
+
+        let over_cap = [
+            item("Strong", [100, 0, 0, 0, 0], [0; 5]),
+            item("Smart", [0, 0, 100, 0, 0], [0; 5]),
+            item("Nimble", [0, 1, 0, 0, 0], [0; 5]),
+        ];
+        assert!(solve(&over_cap) == Err(SolveError::Total { needed: 201 }));
+    }
+
+    #[test]
+    fn lower_bound_rules_out_builds() {
+        let items = [
+            item("Booster", [0; 5], [30, 0, 0, 0, 0]),
+            item("Heavy", [150, 0, 0, 0, 0], [0; 5]),
+        ];
+
+        // the booster helps but the rest is over the cap whatever the order
+        assert_eq!(lower_bound(&items), [120, 0, 0, 0, 0]);
+        assert!(!within_limits(&lower_bound(&items)));
+        assert!(matches!(
+            solve(&items),
+            Err(SolveError::SkillCap { needed: 120, .. })
+        ));
+    }
+
+    #[test]
+    fn gives_up_at_the_limit() {
+        let items = [
+            item("Strong", [10, 0, 0, 0, 0], [0, 5, 0, 0, 0]),
+            item("Swift", [0, 10, 0, 0, 0], [5, 0, 0, 0, 0]),
+        ];
+
+        let (solution, nodes) = solve_limited(&items, 1);
+        assert!(solution == Err(SolveError::GaveUp));
+        assert_eq!(nodes, 1);
+
+        let (solution, _) = solve_limited(&items, usize::MAX);
+        assert!(matches!(solution, Ok(s) if s.total() == 15));
+    }
+}
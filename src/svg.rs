@@ -0,0 +1,100 @@
+//! Standalone SVG rendering of the item tooltip
+
+use crate::tooltip::Line;
+
+/// Height of a line in pixels
+const LINE_HEIGHT: usize = 20;
+const FONT_SIZE: usize = 16;
+/// Width of a character of the fallback monospace font in pixels
+const CHAR_WIDTH: f64 = 9.6;
+const PADDING: usize = 10;
+
+/// Colors of the minecraft tooltip background and border
+const BACKGROUND: &str = "#100010";
+const BORDER_TOP: &str = "#5000ff";
+const BORDER_BOTTOM: &str = "#28007f";
+
+/// Escapes text for use in xml
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Renders the tooltip lines as an svg document
+pub fn render_svg(lines: &[Line]) -> String {
+    let chars = lines
+        .iter()
+        .map(|l| l.text().chars().count())
+        .max()
+        .unwrap_or(0);
+    let width = (chars as f64 * CHAR_WIDTH).ceil() as usize + PADDING * 2;
+    let height = lines.len() * LINE_HEIGHT + PADDING * 2;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    svg.push_str(&format!(
+        r#"<defs><linearGradient id="border" x1="0" y1="0" x2="0" y2="1"><stop offset="0" stop-color="{BORDER_TOP}"/><stop offset="1" stop-color="{BORDER_BOTTOM}"/></linearGradient></defs>"#
+    ));
+    svg.push_str(&format!(
+        r#"<rect x="1" y="1" width="{}" height="{}" rx="3" fill="{BACKGROUND}" fill-opacity="0.94" stroke="url(#border)" stroke-width="2"/>"#,
+        width - 2,
+        height - 2
+    ));
+    svg.push_str(&format!(
+        r#"<g font-family="Minecraft, Minecraftia, 'Courier New', monospace" font-size="{FONT_SIZE}" xml:space="preserve">"#
+    ));
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.spans.is_empty() {
+            continue;
+        }
+
+        let y = PADDING + i * LINE_HEIGHT + FONT_SIZE;
+        svg.push_str(&format!(r#"<text x="{PADDING}" y="{y}">"#));
+        for span in &line.spans {
+            let style = if span.italic {
+                r#" font-style="italic""#
+            } else {
+                ""
+            };
+            svg.push_str(&format!(
+                r#"<tspan fill="{}"{style}>{}</tspan>"#,
+                span.color.hex(),
+                escape(&span.text)
+            ));
+        }
+        svg.push_str("</text>");
+    }
+
+    svg.push_str("</g></svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tooltip::Color;
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(escape(r#"<a & "b">"#), "&lt;a &amp; &quot;b&quot;&gt;");
+        assert_eq!(escape("Leaf Cap"), "Leaf Cap");
+    }
+
+    #[test]
+    fn text_is_escaped_in_the_document() {
+        let svg = render_svg(&[Line::new().span("<Sword & \"Shield\">", Color::Gold)]);
+        assert!(svg.contains("&lt;Sword &amp; &quot;Shield&quot;&gt;"));
+        assert!(!svg.contains("<Sword"));
+    }
+}
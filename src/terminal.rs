@@ -0,0 +1,108 @@
+//! Colored rendering of the item tooltip for terminals
+
+use crate::tooltip::{Color, Line};
+
+/// How colors are written to the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24-bit rgb escapes
+    TrueColor,
+    /// The 256 color palette
+    Palette,
+    /// No escapes at all
+    Plain,
+}
+
+impl ColorMode {
+    /// Parses the value of the `--color` option
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "truecolor" | "24bit" => Some(ColorMode::TrueColor),
+            "256" => Some(ColorMode::Palette),
+            "never" | "plain" => Some(ColorMode::Plain),
+            _ => None,
+        }
+    }
+
+    /// Guesses what the terminal supports from the environment
+    pub fn detect() -> Self {
+        use std::io::IsTerminal;
+
+        let var = |name| std::env::var(name).unwrap_or_default();
+
+        if std::env::var_os("NO_COLOR").is_some()
+            || !std::io::stdout().is_terminal()
+            || var("TERM") == "dumb"
+        {
+            ColorMode::Plain
+        } else if matches!(var("COLORTERM").as_str(), "truecolor" | "24bit") {
+            ColorMode::TrueColor
+        } else {
+            ColorMode::Palette
+        }
+    }
+}
+
+/// The closest color of the 256 color palette.
+///
+/// Picks between the 6x6x6 color cube and the grayscale ramp.
+fn palette_index([r, g, b]: [u8; 3]) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let level = |c: u8| {
+        (0..6)
+            .min_by_key(|&i| (LEVELS[i] as i32 - c as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = [LEVELS[ri], LEVELS[gi], LEVELS[bi]];
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index = ((avg as i32 - 8) / 10).clamp(0, 23) as u8;
+    let gray = 8 + gray_index * 10;
+
+    let dist = |[cr, cg, cb]: [u8; 3]| {
+        [(cr, r), (cg, g), (cb, b)]
+            .iter()
+            .map(|&(a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+
+    if dist([gray; 3]) < dist(cube) {
+        232 + gray_index
+    } else {
+        (16 + 36 * ri + 6 * gi + bi) as u8
+    }
+}
+
+fn escape(color: Color, mode: ColorMode) -> String {
+    let [r, g, b] = color.rgb();
+    match mode {
+        ColorMode::TrueColor => format!("\u{1b}[38;2;{r};{g};{b}m"),
+        ColorMode::Palette => format!("\u{1b}[38;5;{}m", palette_index([r, g, b])),
+        ColorMode::Plain => String::new(),
+    }
+}
+
+/// Renders the tooltip lines for a terminal
+pub fn render_terminal(lines: &[Line], mode: ColorMode) -> String {
+    let mut out = String::new();
+
+    for line in lines {
+        if mode == ColorMode::Plain {
+            out.push_str(&line.text());
+        } else {
+            for span in &line.spans {
+                out.push_str(&escape(span.color, mode));
+                if span.italic {
+                    out.push_str("\u{1b}[3m");
+                }
+                out.push_str(&span.text);
+                out.push_str("\u{1b}[0m");
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
@@ -0,0 +1,127 @@
+//! Plain text exports of the item tooltip for discord and forums
+
+use crate::tooltip::{Color, Line};
+
+/// The discord ansi color closest to a minecraft color.
+///
+/// Discord only knows 8 colors so light and dark variants share one. Gray is left as the default text
+/// color which is closer to it than the dark gray discord uses for 30.
+pub fn ansi_code(color: Color) -> Option<u8> {
+    match color {
+        Color::DarkGray => Some(30),
+        Color::DarkRed | Color::Red => Some(31),
+        Color::DarkGreen | Color::Green => Some(32),
+        Color::Gold | Color::Yellow => Some(33),
+        Color::LightPurple | Color::Purple => Some(35),
+        Color::DarkAqua | Color::Aqua => Some(36),
+        Color::White => Some(37),
+        Color::Gray => None,
+    }
+}
+
+/// Renders the tooltip as a discord ansi code block
+pub fn render_ansi(lines: &[Line]) -> String {
+    let mut out = String::from("```ansi\n");
+
+    for line in lines {
+        let mut current = None;
+        for span in &line.spans {
+            let code = ansi_code(span.color);
+            if current != code {
+                match code {
+                    Some(code) => out.push_str(&format!("\u{1b}[0;{code}m")),
+                    None => out.push_str("\u{1b}[0m"),
+                }
+                current = code;
+            }
+            out.push_str(&span.text);
+        }
+        if current.is_some() {
+            out.push_str("\u{1b}[0m");
+        }
+        out.push('\n');
+    }
+
+    out.push_str("```");
+    out
+}
+
+/// Escapes the characters markdown would treat as formatting
+fn escape_markdown(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "\\*_[]#`<>|~".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Renders the tooltip as markdown with the item name in bold
+pub fn render_markdown(lines: &[Line]) -> String {
+    let mut out = String::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.spans.is_empty() {
+            // a blank line starts a new paragraph
+            if !out.is_empty() && !out.ends_with("\n\n") {
+                out.truncate(out.trim_end_matches([' ', '\n']).len());
+                out.push_str("\n\n");
+            }
+            continue;
+        }
+
+        for (j, span) in line.spans.iter().enumerate() {
+            let text = escape_markdown(&span.text);
+            if i == 0 && j == 0 {
+                out.push_str(&format!("**{text}**"));
+            } else if span.italic && !text.trim().is_empty() {
+                out.push_str(&format!("*{}*", text.trim()));
+            } else {
+                out.push_str(&text);
+            }
+        }
+        // two trailing spaces keep the line break
+        out.push_str("  \n");
+    }
+
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ansi_resets_only_when_the_color_changes() {
+        let lines = [
+            Line::new()
+                .span("Test Wand", Color::Gold)
+                .span(" x", Color::Gray),
+            Line::new(),
+            Line::new().span("a", Color::Red).span("b", Color::DarkRed),
+        ];
+
+        assert_eq!(
+            render_ansi(&lines),
+            "```ansi\n\u{1b}[0;33mTest Wand\u{1b}[0m x\n\n\u{1b}[0;31mab\u{1b}[0m\n```"
+        );
+    }
+
+    #[test]
+    fn markdown_escapes_and_formats() {
+        let lines = [
+            Line::new().span("Test_Wand", Color::Gold),
+            Line::new(),
+            Line::new().span(" A lore line ", Color::DarkGray).italic(),
+            Line::new().span("a|b", Color::Gray),
+            Line::new(),
+        ];
+
+        assert_eq!(
+            render_markdown(&lines),
+            "**Test\\_Wand**\n\n*A lore line*  \na\\|b"
+        );
+    }
+}
@@ -0,0 +1,828 @@
+//! Renderer independent model of the item tooltip
+//!
+//! The tooltip is a list of lines made of colored spans with roles, so the page and every export
+//! render the same thing.
+
+use std::ops::RangeInclusive;
+
+use crate::{
+    damage::{average_damage, id_value},
+    powders::{apply_armour_powders, apply_weapon_powders, powder_special, roman},
+    registry::{major_id_name, IdRegistry, Polarity},
+    skillpoints::skill_point_effects,
+    weights::{weighted_score, WeightProfile},
+    wynn::items::{Id, Item, ItemSet, Powder, Powders, Rarity, Restriction},
+};
+
+/// Minecraft text colors used by tooltips
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    DarkRed,
+    Red,
+    Gold,
+    Yellow,
+    DarkGreen,
+    Green,
+    Aqua,
+    DarkAqua,
+    LightPurple,
+    Purple,
+    White,
+    Gray,
+    DarkGray,
+}
+
+impl Color {
+    /// The color as red, green and blue
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::DarkRed => [0xaa, 0x00, 0x00],
+            Color::Red => [0xff, 0x55, 0x55],
+            Color::Gold => [0xff, 0xaa, 0x00],
+            Color::Yellow => [0xff, 0xff, 0x55],
+            Color::DarkGreen => [0x00, 0xaa, 0x00],
+            Color::Green => [0x55, 0xff, 0x55],
+            Color::Aqua => [0x55, 0xff, 0xff],
+            Color::DarkAqua => [0x00, 0xaa, 0xaa],
+            Color::LightPurple => [0xff, 0x55, 0xff],
+            Color::Purple => [0xaa, 0x00, 0xaa],
+            Color::White => [0xff, 0xff, 0xff],
+            Color::Gray => [0xaa, 0xaa, 0xaa],
+            Color::DarkGray => [0x55, 0x55, 0x55],
+        }
+    }
+
+    /// Name of the color in minecraft text components
+    pub fn name(self) -> &'static str {
+        match self {
+            Color::DarkRed => "dark_red",
+            Color::Red => "red",
+            Color::Gold => "gold",
+            Color::Yellow => "yellow",
+            Color::DarkGreen => "dark_green",
+            Color::Green => "green",
+            Color::Aqua => "aqua",
+            Color::DarkAqua => "dark_aqua",
+            Color::LightPurple => "light_purple",
+            Color::Purple => "dark_purple",
+            Color::White => "white",
+            Color::Gray => "gray",
+            Color::DarkGray => "dark_gray",
+        }
+    }
+
+    /// Legacy formatting code of the color
+    pub fn code(self) -> char {
+        match self {
+            Color::DarkRed => '4',
+            Color::Red => 'c',
+            Color::Gold => '6',
+            Color::Yellow => 'e',
+            Color::DarkGreen => '2',
+            Color::Green => 'a',
+            Color::Aqua => 'b',
+            Color::DarkAqua => '3',
+            Color::LightPurple => 'd',
+            Color::Purple => '5',
+            Color::White => 'f',
+            Color::Gray => '7',
+            Color::DarkGray => '8',
+        }
+    }
+
+    /// The css class of the color
+    pub fn class(self) -> &'static str {
+        match self {
+            Color::DarkRed => "mc-dark-red",
+            Color::Red => "mc-red",
+            Color::Gold => "mc-gold",
+            Color::Yellow => "mc-yellow",
+            Color::DarkGreen => "mc-dark-green",
+            Color::Green => "mc-green",
+            Color::Aqua => "mc-aqua",
+            Color::DarkAqua => "mc-dark-aqua",
+            Color::LightPurple => "mc-light-purple",
+            Color::Purple => "mc-purple",
+            Color::White => "mc-white",
+            Color::Gray => "mc-gray",
+            Color::DarkGray => "mc-dark-gray",
+        }
+    }
+
+    /// The color as a css hex color
+    pub fn hex(self) -> String {
+        let [r, g, b] = self.rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// What a span of the tooltip shows, for renderers which style more than the color
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum Role {
+    /// Labels and other plain text
+    #[default]
+    Text,
+    /// Name of the item or its set
+    Name,
+    /// A stat of the item like damage or defence
+    Stat,
+    /// A stat changed by the applied powders
+    Powdered,
+    /// A roll % or a score
+    Percent,
+    /// The rolled value of an id
+    IdValue,
+    /// Requirements for using the item
+    Requirement,
+    /// Name of a major id
+    MajorId,
+    /// Symbol of an applied powder
+    Powder,
+    /// Rarity, type and restriction of the item
+    Footer,
+    /// Lore of the item
+    Lore,
+    /// Problems with the item data
+    Error,
+}
+
+/// A piece of text with a single style
+#[derive(Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub color: Color,
+    pub italic: bool,
+    pub role: Role,
+}
+
+/// A line of the tooltip, empty lines separate sections
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Line {
+    pub spans: Vec<Span>,
+    /// Worked out by the page rather than shown on the item in game
+    pub computed: bool,
+}
+
+impl Line {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn span(mut self, text: impl Into<String>, color: Color) -> Self {
+        self.spans.push(Span {
+            text: text.into(),
+            color,
+            italic: false,
+            role: Role::Text,
+        });
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        if let Some(span) = self.spans.last_mut() {
+            span.italic = true;
+        }
+        self
+    }
+
+    pub fn computed(mut self) -> Self {
+        self.computed = true;
+        self
+    }
+
+    pub fn role(mut self, role: Role) -> Self {
+        if let Some(span) = self.spans.last_mut() {
+            span.role = role;
+        }
+        self
+    }
+
+    /// Text of the line without any styling
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|s| s.text.as_str()).collect()
+    }
+}
+
+fn mark_computed(lines: &mut [Line]) {
+    for line in lines {
+        line.computed = true;
+    }
+}
+
+/// Everything shown on the tooltip of an item
+pub struct TooltipInput<'a> {
+    pub item: &'a Item,
+    pub ids: &'a [Id],
+    /// Powder slots of the item
+    pub powders: &'a [Option<Powder>],
+    pub rerolls: i32,
+    pub registry: &'a IdRegistry,
+    /// How the overall % in the title is calculated
+    pub overall_mode: OverallMode,
+    /// Skill points assumed for the damage and health calculations
+    pub skill_points: Option<[i32; 5]>,
+    /// Weights to score the rolls with
+    pub profile: Option<&'a WeightProfile>,
+    /// Set the item belongs to
+    pub set: Option<&'a ItemSet>,
+    /// Amount of set pieces assumed to be equipped
+    pub set_pieces: i32,
+}
+
+/// Ways of combining the % of every id into one
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OverallMode {
+    /// Plain average like wynntils
+    Average,
+    /// Ids with wider roll ranges count for more
+    RangeWeighted,
+}
+
+/// Order elements are displayed in on items
+const DISPLAY_ORDER: [Powders; 5] = [
+    Powders::FIRE,
+    Powders::WATER,
+    Powders::AIR,
+    Powders::THUNDER,
+    Powders::EARTH,
+];
+
+pub fn rarity_color(rarity: &Rarity) -> Color {
+    match rarity {
+        Rarity::COMMON | Rarity::INVALID => Color::White,
+        Rarity::UNIQUE => Color::Yellow,
+        Rarity::RARE => Color::LightPurple,
+        Rarity::LEGENDARY => Color::Aqua,
+        Rarity::FABLED => Color::Red,
+        Rarity::MYTHIC => Color::Purple,
+        Rarity::SET => Color::Green,
+    }
+}
+
+pub fn element_color(element: Powders) -> Color {
+    match element {
+        Powders::EARTH => Color::DarkGreen,
+        Powders::THUNDER => Color::Yellow,
+        Powders::WATER => Color::Aqua,
+        Powders::FIRE => Color::Red,
+        Powders::AIR => Color::White,
+    }
+}
+
+/// Color of a roll %
+pub fn percent_color(percent: f64) -> Color {
+    if percent < 30.0 {
+        Color::Red
+    } else if percent < 80.0 {
+        Color::Yellow
+    } else if percent < 97.0 {
+        Color::Green
+    } else {
+        Color::Aqua
+    }
+}
+
+/// Role of a stat which may have been changed by powders
+fn stat_role(powdered: bool) -> Role {
+    if powdered {
+        Role::Powdered
+    } else {
+        Role::Stat
+    }
+}
+
+/// function for adding a + to positive numbers
+pub fn formatnum(num: i32) -> String {
+    if num > 0 {
+        format!("+{}", num)
+    } else {
+        format!("{}", num)
+    }
+}
+
+/// Overall % of the rolls of an item.
+///
+/// Fixed ids and ids which can only roll one value are skipped like wynntils does.
+pub fn overall_percent(ids: &[Id], registry: &IdRegistry, mode: OverallMode) -> Option<f64> {
+    let mut total = 0.0;
+    let mut weights = 0.0;
+
+    for id in ids
+        .iter()
+        .filter(|id| !id.encoding().is_fixed() && id.max_id() != id.min_id())
+    {
+        let weight = match mode {
+            OverallMode::Average => 1.0,
+            OverallMode::RangeWeighted => (id.max_id() - id.min_id()).abs() as f64,
+        };
+
+        total += weight * get_percent(*id.value.get(), id, registry);
+        weights += weight;
+    }
+
+    if weights > 0.0 {
+        Some(total / weights)
+    } else {
+        None
+    }
+}
+
+/// % calc stuff
+pub fn get_percent(value: i32, id: &Id, registry: &IdRegistry) -> f64 {
+    // something stolen from the wynntils code
+    let percent =
+        ((value as f64 - id.min_id() as f64) / (id.max_id() as f64 - id.min_id() as f64)) * 100.0;
+
+    if registry.is_inverted(&id.id) {
+        100.0 - percent
+    } else {
+        percent
+    }
+}
+
+/// Builds the lines of the tooltip of an item
+pub fn tooltip(input: &TooltipInput) -> Vec<Line> {
+    let TooltipInput {
+        item,
+        ids,
+        registry,
+        ..
+    } = *input;
+    let applied = input.powders.iter().flatten().copied().collect::<Vec<_>>();
+    let item_type = &item.item_info.r#type;
+    let item_col = rarity_color(&item.tier);
+
+    let mut lines = Vec::new();
+
+    // name and overall %
+    let mut title = Line::new()
+        .span(item.name.clone(), item_col)
+        .role(Role::Name);
+    if let Some(overall) = overall_percent(ids, registry, input.overall_mode) {
+        title = title
+            .span(format!(" [{overall:.1}%]"), percent_color(overall))
+            .role(Role::Percent);
+    }
+    lines.push(title);
+
+    if let Some(speed) = item.speed {
+        let speed = speed.shift(id_value(ids, "attackSpeedBonus"));
+        lines.push(Line::new().span(format!("{speed} Attack Speed"), Color::Gray));
+    }
+    lines.push(Line::new());
+
+    // damages
+    let ranges = item.damages.as_ref().map(|d| d.ranges());
+    let mut dps = None;
+    if let Some(ranges) = &ranges {
+        let ranges = ranges.clone().map(|r| r.and_then(|r| r.ok()));
+        let weapon_powders = if item_type.is_weapon() {
+            applied.clone()
+        } else {
+            Vec::new()
+        };
+        let damages = apply_weapon_powders(&ranges, &weapon_powders);
+
+        if let Some(d) = damages[0].filter(|d| d.value.max != 0 || !d.powdered) {
+            lines.push(
+                Line::new()
+                    .span(format!("✣ Neutral Damage: {}", d.value), Color::Gold)
+                    .role(stat_role(d.powdered)),
+            );
+        }
+        for element in DISPLAY_ORDER {
+            if let Some(d) = damages[element.index() + 1] {
+                lines.push(
+                    Line::new()
+                        .span(
+                            format!("{} {element} ", element.symbol()),
+                            element_color(element),
+                        )
+                        .span(format!("Damage: {}", d.value), Color::Gray)
+                        .role(stat_role(d.powdered)),
+                );
+            }
+        }
+
+        dps = item.speed.map(|speed| average_damage(&damages, speed, ids));
+    }
+
+    // ranges which could not be read
+    for error in ranges
+        .iter()
+        .flatten()
+        .flatten()
+        .filter_map(|r| r.clone().err())
+    {
+        lines.push(
+            Line::new()
+                .span(format!("✘ {error}"), Color::Red)
+                .role(Role::Error)
+                .computed(),
+        );
+    }
+
+    if let Some(dps) = dps {
+        lines.push(
+            Line::new()
+                .span("Average Damage: ", Color::Gray)
+                .span(format!("{:.1}", dps.per_hit), Color::White)
+                .role(Role::Stat)
+                .computed(),
+        );
+        lines.push(
+            Line::new()
+                .span("Average DPS: ", Color::Gray)
+                .span(format!("{:.1}", dps.per_second), Color::White)
+                .role(Role::Stat)
+                .computed(),
+        );
+    }
+
+    // defences
+    if let Some(defenses) = &item.defenses {
+        if let Some(health) = defenses.health {
+            lines.push(
+                Line::new()
+                    .span(format!("❤ Health: {health}"), Color::DarkRed)
+                    .role(Role::Stat),
+            );
+        }
+
+        let armour_powders = if item_type.is_armour() {
+            applied.clone()
+        } else {
+            Vec::new()
+        };
+        let defs = apply_armour_powders(defenses, &armour_powders);
+        for element in DISPLAY_ORDER {
+            if let Some(d) = defs[element.index()] {
+                lines.push(
+                    Line::new()
+                        .span(
+                            format!("{} {element} ", element.symbol()),
+                            element_color(element),
+                        )
+                        .span(format!("Defence: {}", d.value), Color::Gray)
+                        .role(stat_role(d.powdered)),
+                );
+            }
+        }
+    }
+
+    // effects of the assumed skill points
+    if let Some(assumed) = input.skill_points {
+        let effects = skill_point_effects(item, ids, assumed, dps);
+        let points = effects.points.map(|p| p.to_string()).join("/");
+        let start = lines.len();
+
+        lines.push(Line::new());
+        lines.push(
+            Line::new()
+                .span("With ", Color::Gray)
+                .span(points, Color::White)
+                .span(" skill points", Color::Gray),
+        );
+        lines.push(Line::new().span(
+            format!(
+                "  {:.1}% damage, {:.1}% crit chance",
+                effects.damage_bonus * 100.0,
+                effects.crit_chance * 100.0
+            ),
+            Color::Gray,
+        ));
+        lines.push(Line::new().span(
+            format!(
+                "  {:.1}% resistance, {:.1}% dodge chance",
+                effects.damage_reduction * 100.0,
+                effects.dodge_chance * 100.0
+            ),
+            Color::Gray,
+        ));
+        if let Some(damage) = effects.damage {
+            lines.push(
+                Line::new()
+                    .span("Effective Damage: ", Color::Gray)
+                    .span(format!("{:.1}", damage.per_hit), Color::White)
+                    .role(Role::Stat),
+            );
+            lines.push(
+                Line::new()
+                    .span("Effective DPS: ", Color::Gray)
+                    .span(format!("{:.1}", damage.per_second), Color::White)
+                    .role(Role::Stat),
+            );
+        }
+        lines.push(
+            Line::new()
+                .span("Effective Health: ", Color::Gray)
+                .span(format!("{:.0}", effects.effective_health), Color::DarkRed)
+                .role(Role::Stat)
+                .span(format!(" ({} ❤)", effects.health), Color::Gray),
+        );
+        mark_computed(&mut lines[start..]);
+    }
+
+    // powder special
+    if let Some(special) = powder_special(item_type, &applied) {
+        lines.push(Line::new());
+        lines.push(Line::new().span(
+            format!("{} {}", special.special.name, roman(special.tier)),
+            element_color(special.element),
+        ));
+        for effect in special.special.effects {
+            let value = effect.values[special.tier - 1];
+            lines.push(
+                Line::new()
+                    .span(format!("  - {}: ", effect.name), Color::Gray)
+                    .span(format!("{value}{}", effect.unit), Color::White)
+                    .role(Role::Stat),
+            );
+        }
+    }
+    lines.push(Line::new());
+
+    // requirements
+    let requirements = &item.requirements;
+    let mut requirement =
+        |text: String| lines.push(Line::new().span(text, Color::Gray).role(Role::Requirement));
+    if let Some(class) = requirements.class() {
+        requirement(format!("Class Req: {class}/{}", class.alt_name()));
+    }
+    if let Some(quest) = &requirements.quest {
+        requirement(format!("Quest Req: {quest}"));
+    }
+    if let Some(level) = requirements.level {
+        requirement(format!("Combat Lv. Min: {level}"));
+    }
+    let skills = [
+        "Strength",
+        "Dexterity",
+        "Intelligence",
+        "Defence",
+        "Agility",
+    ];
+    for (name, points) in skills.iter().zip(requirements.skill_points()) {
+        if points != 0 {
+            requirement(format!("{name} Min: {points}"));
+        }
+    }
+    lines.push(Line::new());
+
+    // ids separated by their groups
+    let mut lastgroup: Option<RangeInclusive<i32>> = None;
+    for id in ids {
+        let info = registry.get(id, item_type);
+        let value = *id.value.get();
+
+        let index = info.order.unwrap_or(0);
+        if lastgroup.as_ref().is_some_and(|g| !g.contains(&index)) {
+            lines.push(Line::new());
+        }
+        if info.group.is_some() {
+            lastgroup = info.group.clone();
+        }
+
+        let good = match info.polarity {
+            Polarity::Normal => value >= 0,
+            Polarity::Inverted => value <= 0,
+        };
+        let val_col = if good { Color::Green } else { Color::Red };
+
+        let mut line = Line::new()
+            .span(format!("{}{} ", formatnum(value), info.unit), val_col)
+            .role(Role::IdValue)
+            .span(info.name, Color::Gray);
+        if !info.encoding.is_fixed() {
+            let percent = get_percent(value, id, registry);
+            line = line
+                .span(format!(" [{percent:.3}%]"), percent_color(percent))
+                .role(Role::Percent);
+        }
+        lines.push(line);
+    }
+    lines.push(Line::new());
+
+    // score of the rolls with the selected weights
+    if let Some(profile) = input.profile {
+        if let Some(score) = weighted_score(ids, profile, registry) {
+            lines.push(
+                Line::new()
+                    .span(format!("{} score: ", profile.name), Color::Gray)
+                    .span(format!("{score:.1}%"), percent_color(score))
+                    .role(Role::Percent)
+                    .computed(),
+            );
+            lines.push(Line::new().computed());
+        }
+    }
+
+    // set bonuses
+    if let Some(set) = input.set {
+        let pieces = input.set_pieces.clamp(1, (set.items.len() as i32).max(1)) as usize;
+
+        lines.push(
+            Line::new()
+                .span(
+                    format!("{} Set ({pieces}/{})", set.name, set.items.len()),
+                    Color::Green,
+                )
+                .role(Role::Name),
+        );
+        for name in &set.items {
+            let color = if *name == item.name {
+                Color::Green
+            } else {
+                Color::Gray
+            };
+            lines.push(Line::new().span(format!("- {name}"), color));
+        }
+        lines.push(Line::new());
+
+        // sets missing from the bonus table only list their pieces
+        let has_bonuses = set.bonuses.iter().any(|b| !b.is_empty());
+        if has_bonuses {
+            lines.push(Line::new().span("Set Bonuses:", Color::Green));
+        }
+        for (i, bonuses) in set.bonuses.iter().enumerate() {
+            if bonuses.is_empty() {
+                continue;
+            }
+            let color = if i + 1 == pieces {
+                Color::Green
+            } else {
+                Color::DarkGray
+            };
+
+            lines.push(Line::new().span(format!("{} pieces:", i + 1), color));
+            for bonus in bonuses {
+                lines.push(
+                    Line::new()
+                        .span(
+                            format!(
+                                "  {}{} {}",
+                                formatnum(bonus.value),
+                                registry.unit(&bonus.id, bonus.r#type),
+                                registry.name(&bonus.id, item_type)
+                            ),
+                            color,
+                        )
+                        .role(Role::Stat),
+                );
+            }
+        }
+        if has_bonuses {
+            lines.push(Line::new());
+        }
+    }
+
+    // major ids
+    if !item.major_ids.is_empty() {
+        for major in &item.major_ids {
+            let mut line = Line::new()
+                .span(format!("+{}", major_id_name(&major.name)), Color::Aqua)
+                .role(Role::MajorId);
+            if let Some(description) = &major.description {
+                line = line.span(format!(": {description}"), Color::DarkAqua);
+            }
+            lines.push(line);
+        }
+        lines.push(Line::new());
+    }
+
+    // powder slots
+    if !input.powders.is_empty() {
+        let mut line = Line::new().span(
+            format!("[{}/{}] Powder slots ", applied.len(), input.powders.len()),
+            Color::Gray,
+        );
+        if !applied.is_empty() {
+            line = line.span("[", Color::Gray);
+            for powder in &applied {
+                line = line
+                    .span(powder.element.symbol(), element_color(powder.element))
+                    .role(Role::Powder);
+            }
+            line = line.span("]", Color::Gray);
+        }
+        lines.push(line);
+    }
+
+    // footer
+    let mut footer = format!("{} {}", item.tier, item_type);
+    if input.rerolls > 1 {
+        footer.push_str(&format!(" [{}]", input.rerolls));
+    }
+    lines.push(Line::new().span(footer, item_col).role(Role::Footer));
+
+    if let Some(restriction) = item
+        .restriction
+        .clone()
+        .filter(|r| *r != Restriction::INVALID)
+    {
+        lines.push(
+            Line::new()
+                .span(restriction.to_string(), Color::Red)
+                .role(Role::Footer),
+        );
+    }
+
+    if let Some(lore) = &item.lore {
+        lines.push(Line::new());
+        lines.push(
+            Line::new()
+                .span(lore.clone(), Color::DarkGray)
+                .italic()
+                .role(Role::Lore),
+        );
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use sycamore::reactive::create_rc_signal;
+
+    use super::*;
+    use crate::wynn::items::{Identification, ItemList, StatusType};
+
+    fn registry() -> IdRegistry {
+        let item_list: ItemList = serde_json::from_str(
+            r#"{"items":[],"identificationOrder":{"order":{},"groups":[],"inverted":[]}}"#,
+        )
+        .unwrap();
+        IdRegistry::new(&item_list)
+    }
+
+    fn id(key: &str, baseval: i32, value: i32) -> Id {
+        Id {
+            id: Identification::new(key),
+            idtype: StatusType::PERCENTAGE,
+            fixed: false,
+            baseval,
+            value: create_rc_signal(value),
+        }
+    }
+
+    #[test]
+    fn percent_of_normal_ids() {
+        let registry = registry();
+
+        // 30 to 130
+        assert_eq!(get_percent(30, &id("spellDamage", 100, 0), &registry), 0.0);
+        assert_eq!(get_percent(80, &id("spellDamage", 100, 0), &registry), 50.0);
+        assert_eq!(
+            get_percent(130, &id("spellDamage", 100, 0), &registry),
+            100.0
+        );
+    }
+
+    #[test]
+    fn percent_of_inverted_ids() {
+        let registry = registry();
+        let cost = id("spellCostPct1", -10, 0);
+        assert!(registry.is_inverted(&cost.id));
+
+        // -13 to -7 with the lowest cost being the best roll
+        assert_eq!(get_percent(-13, &cost, &registry), 100.0);
+        assert_eq!(get_percent(-7, &cost, &registry), 0.0);
+        assert_eq!(get_percent(-10, &cost, &registry), 50.0);
+    }
+
+    #[test]
+    fn overall_skips_ids_which_cannot_roll() {
+        let registry = registry();
+
+        let mut fixed = id("rawIntelligence", 7, 7);
+        fixed.fixed = true;
+        // base values of -1 to 1 always roll the same
+        let constant = id("manaRegen", 1, 1);
+        assert!(overall_percent(
+            &[fixed.clone(), constant.clone()],
+            &registry,
+            OverallMode::Average
+        )
+        .is_none());
+
+        let ids = [fixed, constant, id("spellDamage", 100, 130)];
+        assert_eq!(
+            overall_percent(&ids, &registry, OverallMode::Average),
+            Some(100.0)
+        );
+    }
+
+    #[test]
+    fn overall_modes() {
+        let registry = registry();
+        // 0% on a range of 6 and 100% on a range of 100
+        let ids = [id("spellCostPct1", -10, -7), id("spellDamage", 100, 130)];
+
+        assert_eq!(
+            overall_percent(&ids, &registry, OverallMode::Average),
+            Some(50.0)
+        );
+        let weighted = overall_percent(&ids, &registry, OverallMode::RangeWeighted).unwrap();
+        assert!((weighted - 100.0 * 100.0 / 106.0).abs() < 1e-9);
+    }
+}
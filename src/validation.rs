@@ -0,0 +1,80 @@
+//! Validation of the values going into a generated id string
+
+use crate::{
+    encoding::{encode_char, encode_value, represented_value, MAX_ENCODED},
+    registry::IdRegistry,
+    wynn::items::{Id, Item},
+};
+
+/// How bad a problem is
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The string works but may not be what the user expects
+    Warning,
+    /// The string would be broken or obviously fake
+    Error,
+}
+
+/// A single problem found in the values of an item
+#[derive(Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Problem {
+    fn warning(message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message,
+        }
+    }
+
+    fn error(message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            message,
+        }
+    }
+}
+
+/// Checks the values of an item before they are encoded into an id string
+pub fn validate(item: &Item, ids: &[Id], registry: &IdRegistry, rerolls: i32) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    for id in ids {
+        // fixed ids are not stored in the string
+        if id.encoding().is_fixed() {
+            continue;
+        }
+
+        let value = *id.value.get();
+        let name = registry.name(&id.id, &item.item_info.r#type);
+
+        if !(id.min_id()..=id.max_id()).contains(&value) {
+            problems.push(Problem::error(format!(
+                "{name} is {value} but can only roll between {} and {}",
+                id.min_id(),
+                id.max_id()
+            )));
+        } else if encode_char(encode_value(id, value)).is_none() {
+            problems.push(Problem::error(format!(
+                "{name} cannot be encoded with the value {value}, try a value closer to its base value of {}",
+                id.baseval
+            )));
+        } else if represented_value(id, value) != value {
+            problems.push(Problem::warning(format!(
+                "{name} is set to {value} but recipients will see {}",
+                represented_value(id, value)
+            )));
+        }
+    }
+
+    if encode_char(rerolls).is_none() {
+        problems.push(Problem::error(format!(
+            "The item cannot be rerolled {rerolls} times, the reroll count has to be between 0 and {MAX_ENCODED}"
+        )));
+    }
+
+    problems
+}
@@ -0,0 +1,360 @@
+//! User defined weights for judging how good the rolls of an item are for a build
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sycamore::prelude::*;
+
+use crate::{
+    copy_to_clipboard,
+    registry::{base_name, IdRegistry},
+    tooltip::get_percent,
+    wynn::items::{Id, Identification},
+};
+
+/// Key the profiles are saved under in local storage
+const STORAGE_KEY: &str = "idmangler-weight-profiles";
+
+/// Weights of the ids a build cares about
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct WeightProfile {
+    pub name: String,
+    /// Weight of every id, missing ids are ignored.
+    ///
+    /// Negative weights prefer bad rolls of the id.
+    pub weights: BTreeMap<Identification, f64>,
+}
+
+impl WeightProfile {
+    fn preset(name: &str, weights: &[(&str, f64)]) -> Self {
+        Self {
+            name: name.to_string(),
+            weights: weights
+                .iter()
+                .map(|(key, weight)| (Identification::new(*key), *weight))
+                .collect(),
+        }
+    }
+
+    pub fn weight(&self, id: &Identification) -> f64 {
+        self.weights.get(id).copied().unwrap_or(0.0)
+    }
+}
+
+/// Profiles for common builds
+pub fn presets() -> Vec<WeightProfile> {
+    vec![
+        WeightProfile::preset(
+            "Spell damage",
+            &[
+                ("spellDamage", 1.0),
+                ("spellDamageRaw", 1.0),
+                ("rainbowSpellDamageRaw", 0.5),
+                ("manaRegen", 1.0),
+                ("manaSteal", 0.5),
+                ("spellCostPct1", 0.5),
+                ("spellCostRaw1", 0.5),
+                ("spellCostPct3", 0.5),
+                ("spellCostRaw3", 0.5),
+            ],
+        ),
+        WeightProfile::preset(
+            "Melee",
+            &[
+                ("damageBonus", 1.0),
+                ("damageBonusRaw", 1.0),
+                ("attackSpeedBonus", 1.0),
+                ("lifeSteal", 0.5),
+                ("rawStrength", 0.5),
+                ("rawDexterity", 0.5),
+            ],
+        ),
+        WeightProfile::preset(
+            "Tank",
+            &[
+                ("healthBonus", 1.0),
+                ("healthRegen", 0.5),
+                ("healthRegenRaw", 0.5),
+                ("lifeSteal", 0.5),
+                ("rawDefence", 0.5),
+            ],
+        ),
+    ]
+}
+
+/// Weighted average of the roll % of the ids of an item.
+///
+/// Returns `None` if the profile gives no weight to any of the rollable ids.
+pub fn weighted_score(ids: &[Id], profile: &WeightProfile, registry: &IdRegistry) -> Option<f64> {
+    let mut total = 0.0;
+    let mut weights = 0.0;
+
+    for id in ids.iter().filter(|id| !id.encoding().is_fixed()) {
+        let weight = profile.weight(&id.id);
+        if weight == 0.0 {
+            continue;
+        }
+
+        // the % is already flipped for inverted ids so a negative weight flips it back
+        let percent = get_percent(*id.value.get(), id, registry);
+        total += if weight > 0.0 {
+            weight * percent
+        } else {
+            -weight * (100.0 - percent)
+        };
+        weights += f64::abs(weight);
+    }
+
+    if weights > 0.0 {
+        Some(total / weights)
+    } else {
+        None
+    }
+}
+
+/// Weighted sum of id values for comparing different items.
+///
+/// Each value is divided by the largest roll of the id so ids with big numbers like health do not
+/// drown out the rest. Values of inverted ids count negatively so lower spell costs score higher.
+pub fn weighted_total<'a>(
+    values: impl IntoIterator<Item = (&'a Identification, f64)>,
+    profile: &WeightProfile,
+    registry: &IdRegistry,
+) -> f64 {
+    values
+        .into_iter()
+        .map(|(id, value)| {
+            let sign = if registry.is_inverted(id) { -1.0 } else { 1.0 };
+            profile.weight(id) * value / registry.scale(id) * sign
+        })
+        .sum()
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// Loads the saved profiles with any missing presets added
+pub fn load_profiles() -> Vec<WeightProfile> {
+    let profiles: Vec<WeightProfile> = storage()
+        .and_then(|s| s.get_item(STORAGE_KEY).ok()?)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    with_presets(profiles)
+}
+
+/// Adds the presets missing from the profiles
+fn with_presets(mut profiles: Vec<WeightProfile>) -> Vec<WeightProfile> {
+    for preset in presets() {
+        if !profiles.iter().any(|p| p.name == preset.name) {
+            profiles.push(preset);
+        }
+    }
+
+    profiles
+}
+
+fn save_profiles(profiles: &[WeightProfile]) {
+    if let (Some(storage), Ok(json)) = (storage(), serde_json::to_string(profiles)) {
+        let _ = storage.set_item(STORAGE_KEY, &json);
+    }
+}
+
+#[derive(Prop)]
+pub struct WeightEditorProps<'a> {
+    registry: &'a IdRegistry,
+    profiles: &'a Signal<Vec<WeightProfile>>,
+    /// Name of the selected profile
+    selected: &'a Signal<String>,
+}
+
+#[component]
+pub fn WeightEditor<'a, G: Html>(cx: Scope<'a>, props: WeightEditorProps<'a>) -> View<G> {
+    let profiles = props.profiles;
+    let selected = props.selected;
+
+    let names = create_selector(cx, move || {
+        profiles
+            .get()
+            .iter()
+            .map(|p| p.name.clone())
+            .collect::<Vec<_>>()
+    });
+
+    let exported = create_selector(cx, move || {
+        profiles
+            .get()
+            .iter()
+            .find(|p| p.name == *selected.get())
+            .and_then(|p| serde_json::to_string_pretty(p).ok())
+            .unwrap_or_default()
+    });
+
+    // changes the selected profile and saves the result
+    let update = move |f: &dyn Fn(&mut WeightProfile)| {
+        let mut list = (*profiles.get_untracked()).clone();
+        if let Some(profile) = list
+            .iter_mut()
+            .find(|p| p.name == *selected.get_untracked())
+        {
+            f(profile);
+        }
+        save_profiles(&list);
+        profiles.set(list);
+    };
+
+    let new_name = create_signal(cx, String::new());
+    let save_copy = move |_| {
+        let name = new_name.get().trim().to_string();
+        if name.is_empty() || names.get().contains(&name) {
+            return;
+        }
+
+        let mut list = (*profiles.get()).clone();
+        let mut profile = list
+            .iter()
+            .find(|p| p.name == *selected.get())
+            .cloned()
+            .unwrap_or(WeightProfile {
+                name: String::new(),
+                weights: BTreeMap::new(),
+            });
+        profile.name = name.clone();
+        list.push(profile);
+        save_profiles(&list);
+        profiles.set(list);
+        selected.set(name);
+        new_name.set(String::new());
+    };
+
+    // deleting a preset resets it to its default weights
+    let delete = move |_| {
+        let mut list = (*profiles.get()).clone();
+        list.retain(|p| p.name != *selected.get());
+        save_profiles(&list);
+        profiles.set(with_presets(list));
+        selected.set(String::new());
+    };
+
+    let import = create_signal(cx, String::new());
+    let import_error = create_signal(cx, None::<String>);
+    let import_note = create_signal(cx, None::<String>);
+    let do_import = move |_| {
+        import_note.set(None);
+        match serde_json::from_str::<WeightProfile>(&import.get()) {
+            // an empty name would be the same as selecting no profile
+            Ok(profile) if profile.name.trim().is_empty() => {
+                import_error.set(Some(String::from("The profile needs a name")))
+            }
+            Ok(mut profile) => {
+                profile.name = profile.name.trim().to_string();
+
+                let mut list = (*profiles.get()).clone();
+                let before = list.len();
+                list.retain(|p| p.name != profile.name);
+                if list.len() != before {
+                    import_note.set(Some(format!(
+                        "Replaced the existing profile {}",
+                        profile.name
+                    )));
+                }
+
+                let name = profile.name.clone();
+                list.push(profile);
+                save_profiles(&list);
+                profiles.set(list);
+                selected.set(name);
+                import.set(String::new());
+                import_error.set(None);
+            }
+            Err(e) => import_error.set(Some(e.to_string())),
+        }
+    };
+
+    view! {cx,
+        form(class="pure-form", style="display: flex; gap: 5px;") {
+            select(style="flex-grow: 1;", bind:value=selected) {
+                option(value="") {"None"}
+                Indexed (
+                    iterable=names,
+                    view=|cx, name| {
+                        let label = name.clone();
+                        view! {cx, option(value=name) {(label)}}
+                    }
+                )
+            }
+            button(class="pure-button", prop:type="button", disabled=selected.get().is_empty(), on:click=delete) {"Delete"}
+        }
+        (if !selected.get().is_empty() {
+            let profile = profiles
+                .get_untracked()
+                .iter()
+                .find(|p| p.name == *selected.get())
+                .cloned();
+
+            let inputs = View::new_fragment(
+                props
+                    .registry
+                    .sorted()
+                    .into_iter()
+                    .map(|id| {
+                        let id = id.clone();
+                        let weight = profile.as_ref().map(|p| p.weight(&id)).unwrap_or(0.0);
+                        let value = create_signal(cx, weight.to_string());
+                        let name = base_name(&id);
+
+                        let set_weight = move |_| {
+                            if let Ok(weight) = value.get().parse::<f64>() {
+                                update(&|profile| {
+                                    if weight == 0.0 {
+                                        profile.weights.remove(&id);
+                                    } else {
+                                        profile.weights.insert(id.clone(), weight);
+                                    }
+                                });
+                            }
+                        };
+
+                        view! {cx,
+                            label(style="float: left;") {(name)}
+                            input(style="float: right; width: 80px;", prop:type="number", step="0.1", bind:value=value, on:input=set_weight)
+                            br(style="clear: both;") {}
+                        }
+                    })
+                    .collect(),
+            );
+
+            view! {cx,
+                details {
+                    summary {"Weights"}
+                    form(class="pure-form") {(inputs)}
+                }
+            }
+        } else {view!{cx,}})
+        form(class="pure-form", style="display: flex; gap: 5px; margin-top: 5px;") {
+            input(style="flex-grow: 1;", placeholder="New profile name", bind:value=new_name)
+            button(class="pure-button", prop:type="button", on:click=save_copy) {"Save copy"}
+        }
+        details {
+            summary {"Export / import"}
+            form(class="pure-form") {
+                textarea(style="width: 100%;", readonly=true, prop:value=exported.get().to_string())
+                button(
+                    class="pure-button",
+                    prop:type="button",
+                    style="width: 100%;",
+                    on:click=move |_| copy_to_clipboard(&exported.get())
+                ) {"Copy profile"}
+                textarea(style="width: 100%; margin-top: 5px;", placeholder="Paste a profile", bind:value=import)
+                button(class="pure-button", prop:type="button", style="width: 100%;", on:click=do_import) {"Import profile"}
+            }
+            (if let Some(e) = (*import_error.get()).clone() {
+                view! {cx, p(class="problem mc-red") {(e)}}
+            } else {view!{cx,}})
+            (if let Some(note) = (*import_note.get()).clone() {
+                view! {cx, p(class="problem mc-gold") {(note)}}
+            } else {view!{cx,}})
+        }
+    }
+}
@@ -3,8 +3,8 @@
 
 /// Item information and data
 pub mod items {
-    use serde::Deserialize;
-    use std::{collections::BTreeMap, fmt::Display, ops::RangeInclusive};
+    use serde::{Deserialize, Serialize};
+    use std::{collections::BTreeMap, fmt::Display, ops::RangeInclusive, str::FromStr};
     use sycamore::reactive::RcSignal;
 
     /// All possible rarities of items
@@ -23,6 +23,18 @@ pub mod items {
         INVALID,
     }
 
+    impl Rarity {
+        pub const ALL: [Rarity; 7] = [
+            Rarity::COMMON,
+            Rarity::UNIQUE,
+            Rarity::RARE,
+            Rarity::LEGENDARY,
+            Rarity::FABLED,
+            Rarity::MYTHIC,
+            Rarity::SET,
+        ];
+    }
+
     // implement display for rarity
     impl Display for Rarity {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -81,37 +93,110 @@ pub mod items {
         }
     }
 
+    impl Type {
+        pub const ALL: [Type; 12] = [
+            Type::SPEAR,
+            Type::WAND,
+            Type::BOW,
+            Type::DAGGER,
+            Type::RELIK,
+            Type::HELMET,
+            Type::CHESTPLATE,
+            Type::LEGGINGS,
+            Type::BOOTS,
+            Type::RING,
+            Type::BRACELET,
+            Type::NECKLACE,
+        ];
+
+        /// Whether the item is a weapon
+        pub fn is_weapon(&self) -> bool {
+            matches!(
+                self,
+                Type::SPEAR | Type::WAND | Type::BOW | Type::DAGGER | Type::RELIK
+            )
+        }
+
+        /// The class which can use the item if it is a weapon
+        pub fn class(&self) -> Option<Class> {
+            match self {
+                Type::SPEAR => Some(Class::WARRIOR),
+                Type::BOW => Some(Class::ARCHER),
+                Type::WAND => Some(Class::MAGE),
+                Type::DAGGER => Some(Class::ASSASSIN),
+                Type::RELIK => Some(Class::SHAMAN),
+                _ => None,
+            }
+        }
+
+        /// Whether the item is armour
+        pub fn is_armour(&self) -> bool {
+            matches!(
+                self,
+                Type::HELMET | Type::CHESTPLATE | Type::LEGGINGS | Type::BOOTS
+            )
+        }
+    }
+
+    /// Player classes
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum Class {
+        WARRIOR,
+        ARCHER,
+        MAGE,
+        ASSASSIN,
+        SHAMAN,
+    }
+
+    impl Class {
+        /// Names of the spells of the class from the 1st to the 4th spell
+        pub fn spell_names(self) -> [&'static str; 4] {
+            match self {
+                Class::WARRIOR => ["Bash", "Charge", "Uppercut", "War Scream"],
+                Class::ARCHER => ["Arrow Storm", "Escape", "Arrow Bomb", "Arrow Shield"],
+                Class::MAGE => ["Heal", "Teleport", "Meteor", "Ice Snake"],
+                Class::ASSASSIN => ["Spin Attack", "Vanish", "Multihit", "Smoke Bomb"],
+                Class::SHAMAN => ["Totem", "Haul", "Aura", "Uproot"],
+            }
+        }
+
+        /// Name of the reskinned version of the class
+        pub fn alt_name(self) -> &'static str {
+            match self {
+                Class::WARRIOR => "Knight",
+                Class::ARCHER => "Hunter",
+                Class::MAGE => "Dark Wizard",
+                Class::ASSASSIN => "Ninja",
+                Class::SHAMAN => "Skyseer",
+            }
+        }
+    }
+
+    impl Display for Class {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Class::WARRIOR => "Warrior".fmt(f),
+                Class::ARCHER => "Archer".fmt(f),
+                Class::MAGE => "Mage".fmt(f),
+                Class::ASSASSIN => "Assassin".fmt(f),
+                Class::SHAMAN => "Shaman".fmt(f),
+            }
+        }
+    }
+
     /// struct representing identification types
     #[allow(non_camel_case_types)]
-    #[derive(Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+    #[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
     pub struct Identification(String);
 
-    impl Display for Identification {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            // handle some ids seperately and use general case for everything that works
-            match self.0.to_lowercase().as_str() {
-                "rawstrength" => "Strength".fmt(f),
-                "rawdexterity" => "Dexterity".fmt(f),
-                "rawintelligence" => "Intelligence".fmt(f),
-                "rawdefence" => "Defence".fmt(f),
-                "rawagility" => "Agility".fmt(f),
-                "xpbonus" => "XP Bonus".fmt(f),
-                _ => {
-                    let mut s = String::new();
-                    let mut itr = self.0.chars();
-
-                    s.push(itr.next().unwrap().to_ascii_uppercase());
-
-                    for c in itr {
-                        if c.is_uppercase() {
-                            s.push(' ');
-                        }
-                        s.push(c);
-                    }
-
-                    s.fmt(f)
-                }
-            }
+    impl Identification {
+        pub fn new(key: impl Into<String>) -> Self {
+            Self(key.into())
+        }
+
+        /// Key of the id in the wynntils api
+        pub fn key(&self) -> &str {
+            &self.0
         }
     }
 
@@ -141,6 +226,38 @@ pub mod items {
         }
     }
 
+    impl AttackSpeed {
+        /// All attack speeds from slowest to fastest
+        const ALL: [AttackSpeed; 7] = [
+            AttackSpeed::SUPER_SLOW,
+            AttackSpeed::VERY_SLOW,
+            AttackSpeed::SLOW,
+            AttackSpeed::NORMAL,
+            AttackSpeed::FAST,
+            AttackSpeed::VERY_FAST,
+            AttackSpeed::SUPER_FAST,
+        ];
+
+        /// Attack speed shifted by a number of tiers, stopping at the slowest and fastest speeds
+        pub fn shift(self, tiers: i32) -> AttackSpeed {
+            let i = Self::ALL.iter().position(|s| *s == self).unwrap_or(0) as i32;
+            Self::ALL[(i + tiers).clamp(0, Self::ALL.len() as i32 - 1) as usize]
+        }
+
+        /// Amount of attacks per second
+        pub fn hits_per_second(self) -> f64 {
+            match self {
+                AttackSpeed::SUPER_SLOW => 0.51,
+                AttackSpeed::VERY_SLOW => 0.83,
+                AttackSpeed::SLOW => 1.5,
+                AttackSpeed::NORMAL => 2.05,
+                AttackSpeed::FAST => 2.5,
+                AttackSpeed::VERY_FAST => 3.1,
+                AttackSpeed::SUPER_FAST => 4.3,
+            }
+        }
+    }
+
     #[derive(Deserialize, Clone)]
     pub struct ItemList {
         pub items: Vec<Item>,
@@ -175,11 +292,93 @@ pub mod items {
         pub speed: Option<AttackSpeed>,
         /// Statuses or the ids of the item
         pub statuses: BTreeMap<Identification, StatusId>,
+        /// Major ids of the item
+        #[serde(rename = "majorIds", default)]
+        pub major_ids: Vec<MajorId>,
+        /// Trading restrictions of the item
+        pub restriction: Option<Restriction>,
+        /// Lore text of the item
+        pub lore: Option<String>,
+    }
+
+    /// A major id of an item
+    #[derive(Deserialize, Clone, PartialEq, Eq)]
+    #[serde(from = "MajorIdRepr")]
+    pub struct MajorId {
+        pub name: String,
+        pub description: Option<String>,
+    }
+
+    /// Major ids can be either just a name or a name with a description
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MajorIdRepr {
+        Name(String),
+        Full {
+            name: String,
+            #[serde(default)]
+            description: Option<String>,
+        },
+    }
+
+    impl From<MajorIdRepr> for MajorId {
+        fn from(repr: MajorIdRepr) -> Self {
+            match repr {
+                MajorIdRepr::Name(name) => MajorId {
+                    name,
+                    description: None,
+                },
+                MajorIdRepr::Full { name, description } => MajorId { name, description },
+            }
+        }
+    }
+
+    /// Trading restrictions of items
+    #[allow(non_camel_case_types)]
+    #[derive(Deserialize, Clone, PartialEq, Eq)]
+    pub enum Restriction {
+        #[serde(alias = "untradable", alias = "Untradable")]
+        UNTRADABLE,
+        #[serde(alias = "quest item", alias = "Quest Item")]
+        QUEST_ITEM,
+
+        #[serde(other)]
+        INVALID,
+    }
+
+    impl Display for Restriction {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Restriction::UNTRADABLE => "Untradable Item".fmt(f),
+                Restriction::QUEST_ITEM => "Quest Item".fmt(f),
+                Restriction::INVALID => "".fmt(f),
+            }
+        }
     }
 
     #[derive(Deserialize, Clone, PartialEq, Eq)]
     pub struct ItemInfo {
         pub r#type: Type,
+        /// Name of the set the item belongs to
+        pub set: Option<String>,
+    }
+
+    /// A set of items giving bonuses when multiple pieces are worn
+    #[derive(Deserialize, Clone, PartialEq, Eq)]
+    pub struct ItemSet {
+        pub name: String,
+        /// Names of the items in the set
+        pub items: Vec<String>,
+        /// Bonus ids for wearing 1 to n pieces of the set
+        pub bonuses: Vec<Vec<SetBonus>>,
+    }
+
+    /// A single id given by a set bonus
+    #[derive(Deserialize, Clone, PartialEq, Eq)]
+    pub struct SetBonus {
+        pub id: Identification,
+        pub r#type: StatusType,
+        pub value: i32,
     }
 
     /// requirements of an item
@@ -191,6 +390,37 @@ pub mod items {
         pub intelligence: Option<i32>,
         pub defense: Option<i32>,
         pub agility: Option<i32>,
+        /// Quest which has to be completed to use the item
+        pub quest: Option<String>,
+        /// Class which can use the item
+        #[serde(rename = "classType")]
+        pub class_type: Option<String>,
+    }
+
+    impl Requirements {
+        /// Class which can use the item if there is a class requirement
+        pub fn class(&self) -> Option<Class> {
+            match self.class_type.as_ref()?.to_uppercase().as_str() {
+                "WARRIOR" => Some(Class::WARRIOR),
+                "ARCHER" => Some(Class::ARCHER),
+                "MAGE" => Some(Class::MAGE),
+                "ASSASSIN" => Some(Class::ASSASSIN),
+                "SHAMAN" => Some(Class::SHAMAN),
+                _ => None,
+            }
+        }
+
+        /// Skill point requirements in the strength, dexterity, intelligence, defence, agility order
+        pub fn skill_points(&self) -> [i32; 5] {
+            [
+                self.strength,
+                self.dexterity,
+                self.intelligence,
+                self.defense,
+                self.agility,
+            ]
+            .map(|r| r.unwrap_or(0))
+        }
     }
 
     /// damagetypes of the item
@@ -204,6 +434,68 @@ pub mod items {
         pub air: Option<String>,
     }
 
+    impl DamageTypes {
+        /// Parsed damages in the neutral, earth, thunder, water, fire, air order
+        pub fn ranges(&self) -> [Option<Result<DamageRange, DamageRangeError>>; 6] {
+            [
+                &self.neutral,
+                &self.earth,
+                &self.thunder,
+                &self.water,
+                &self.fire,
+                &self.air,
+            ]
+            .map(|d| d.as_ref().map(|d| d.parse()))
+        }
+    }
+
+    /// A range of damage values
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct DamageRange {
+        pub min: i32,
+        pub max: i32,
+    }
+
+    impl DamageRange {
+        pub fn average(&self) -> f64 {
+            (self.min + self.max) as f64 / 2.0
+        }
+    }
+
+    impl Display for DamageRange {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            format!("{}-{}", self.min, self.max).fmt(f)
+        }
+    }
+
+    /// Error for damage values not in the "min-max" format
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct DamageRangeError(pub String);
+
+    impl Display for DamageRangeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            format!("\"{}\" is not a valid damage range", self.0).fmt(f)
+        }
+    }
+
+    impl FromStr for DamageRange {
+        type Err = DamageRangeError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let err = || DamageRangeError(s.to_string());
+
+            let (min, max) = s.split_once('-').ok_or_else(err)?;
+            let min = min.trim().parse().map_err(|_| err())?;
+            let max = max.trim().parse().map_err(|_| err())?;
+
+            if min > max {
+                return Err(err());
+            }
+
+            Ok(DamageRange { min, max })
+        }
+    }
+
     /// defensetypes of the item
     #[derive(Deserialize, Clone, PartialEq, Eq)]
     pub struct DefenseTypes {
@@ -270,6 +562,18 @@ pub mod items {
         AIR,
     }
 
+    impl Display for Powders {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Powders::EARTH => "Earth".fmt(f),
+                Powders::THUNDER => "Thunder".fmt(f),
+                Powders::WATER => "Water".fmt(f),
+                Powders::FIRE => "Fire".fmt(f),
+                Powders::AIR => "Air".fmt(f),
+            }
+        }
+    }
+
     impl Powders {
         pub fn from_i32(n: i32) -> Option<Self> {
             match n {
@@ -291,6 +595,30 @@ pub mod items {
                 Powders::AIR => 5,
             }
         }
+
+        /// Index of the element in the earth, thunder, water, fire, air order
+        pub fn index(self) -> usize {
+            self.to_i32() as usize - 1
+        }
+
+        /// Symbol of the element shown on items
+        pub fn symbol(self) -> &'static str {
+            match self {
+                Powders::EARTH => "✤",
+                Powders::THUNDER => "✦",
+                Powders::WATER => "✽",
+                Powders::FIRE => "✹",
+                Powders::AIR => "❋",
+            }
+        }
+    }
+
+    /// A powder of a specific tier applied to an item
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Powder {
+        pub element: Powders,
+        /// Tier of the powder from 1 to 6
+        pub tier: u8,
     }
 
     #[derive(PartialEq, Eq, Clone)]
@@ -304,7 +632,7 @@ pub mod items {
 
     impl Id {
         pub fn max_id(&self) -> i32 {
-            if self.fixed || (-1 <= self.baseval && self.baseval <= 1) {
+            if self.encoding().is_fixed() {
                 self.baseval
             } else if self.baseval < 1 {
                 f64::round(self.baseval as f64 * 0.7) as i32
@@ -313,7 +641,7 @@ pub mod items {
             }
         }
         pub fn min_id(&self) -> i32 {
-            if self.fixed || (-1 <= self.baseval && self.baseval <= 1) {
+            if self.encoding().is_fixed() {
                 self.baseval
             } else if self.baseval < 1 {
                 f64::round(self.baseval as f64 * 1.3) as i32
@@ -321,5 +649,66 @@ pub mod items {
                 f64::round(self.baseval as f64 * 0.3) as i32
             }
         }
+        /// How the id is stored in id strings
+        pub fn encoding(&self) -> Encoding {
+            if self.fixed {
+                Encoding::Fixed
+            } else if -1 <= self.baseval && self.baseval <= 1 {
+                Encoding::Constant
+            } else if i32::abs(self.baseval) > 100 {
+                Encoding::Percent
+            } else {
+                Encoding::Value
+            }
+        }
+    }
+
+    /// How the value of an id is stored in id strings
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum Encoding {
+        /// The id is not stored at all
+        Fixed,
+        /// The id can only have one value but still takes up a character
+        Constant,
+        /// The value is stored relative to the minimum roll
+        Value,
+        /// The value is stored as a rounded % of the base value
+        Percent,
+    }
+
+    impl Encoding {
+        /// Whether the value of the id cannot be changed
+        pub fn is_fixed(self) -> bool {
+            matches!(self, Encoding::Fixed | Encoding::Constant)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::items::DamageRange;
+
+    fn parse(s: &str) -> Option<(i32, i32)> {
+        s.parse::<DamageRange>().ok().map(|r| (r.min, r.max))
+    }
+
+    #[test]
+    fn damage_ranges() {
+        assert_eq!(parse("20-30"), Some((20, 30)));
+        assert_eq!(parse(" 5 - 7 "), Some((5, 7)));
+        assert_eq!(parse("0-0"), Some((0, 0)));
+        assert!("0-0"
+            .parse::<DamageRange>()
+            .is_ok_and(|r| r.average() == 0.0));
+        assert!("20-31"
+            .parse::<DamageRange>()
+            .is_ok_and(|r| r.average() == 25.5));
+    }
+
+    #[test]
+    fn malformed_damage_ranges() {
+        for s in ["", "20", "20-", "-30", "a-b", "20-30-40", "30-20"] {
+            assert_eq!(parse(s), None, "{s:?}");
+        }
     }
 }